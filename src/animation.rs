@@ -0,0 +1,195 @@
+use crate::game::Card;
+
+pub const DURATION: f64 = 450.0; // ms
+pub const STAGGER: f64 = 90.0; // ms between cards moving together
+
+// A position on the screen, as a percentage of the container plus an offset in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: (f64, f64), // %, px
+    pub y: (f64, f64), // %, px
+}
+
+impl Point {
+    pub const DECK: Point = Point {
+        x: (50.0, -110.0),
+        y: (50.0, 0.0),
+    };
+    pub const PILE: Point = Point {
+        x: (50.0, 110.0),
+        y: (50.0, 0.0),
+    };
+    pub const HAND: Point = Point {
+        x: (50.0, 0.0),
+        y: (100.0, -160.0),
+    };
+
+    // A seat on the table, given as percentages of the table's size
+    pub fn seat((x, y): (f64, f64)) -> Point {
+        Point {
+            x: (50.0, (x - 50.0) * 6.4),
+            y: (50.0, -60.0 + (y - 50.0) * 3.8),
+        }
+    }
+
+    pub fn lerp(&self, to: &Point, t: f64) -> Point {
+        let lerp = |a: (f64, f64), b: (f64, f64)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+
+        Point {
+            x: lerp(self.x, to.x),
+            y: lerp(self.y, to.y),
+        }
+    }
+
+    pub fn style(&self) -> String {
+        format!(
+            "left: calc({:.2}% + {:.1}px); top: calc({:.2}% + {:.1}px);",
+            self.x.0, self.x.1, self.y.0, self.y.1
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    pub card: Option<Card>, // None flies face down
+    pub from: Point,
+    pub to: Point,
+    pub start: f64,
+}
+
+impl Animation {
+    // Eased progress between 0 and 1, None if the animation hasn't started yet
+    pub fn progress(&self, now: f64) -> Option<f64> {
+        if now < self.start {
+            return None;
+        }
+
+        let t = ((now - self.start) / DURATION).min(1.0);
+        Some(1.0 - (1.0 - t).powi(3))
+    }
+
+    pub fn position(&self, now: f64) -> Option<Point> {
+        self.progress(now).map(|t| self.from.lerp(&self.to, t))
+    }
+}
+
+// Keeps the animations in flight, driven by animation frames from the render service
+pub struct Scheduler {
+    pub animations: Vec<Animation>,
+    pub now: f64,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler {
+            animations: Vec::new(),
+            now: 0.0,
+        }
+    }
+
+    // Cards moving together leave one after another
+    pub fn push(&mut self, cards: Vec<Option<Card>>, from: Point, to: Point, now: f64) {
+        let queued = self
+            .animations
+            .iter()
+            .filter(|a| a.from == from && a.to == to && a.start > now - DURATION)
+            .count();
+
+        cards.into_iter().enumerate().for_each(|(i, card)| {
+            self.animations.push(Animation {
+                card,
+                from,
+                to,
+                start: now + (queued + i) as f64 * STAGGER,
+            });
+        });
+    }
+
+    // Drops finished animations, returns whether any are left
+    pub fn tick(&mut self, now: f64) -> bool {
+        self.now = now;
+        self.animations.retain(|a| now < a.start + DURATION);
+
+        self.is_running()
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    pub fn in_flight(&self) -> impl Iterator<Item = (&Animation, Point)> {
+        let now = self.now;
+
+        self.animations
+            .iter()
+            .filter_map(move |a| a.position(now).map(|p| (a, p)))
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Cards added to and removed from a hand, duplicates are counted separately
+pub fn diff_hand(old: &[Card], new: &[Card]) -> (Vec<Card>, Vec<Card>) {
+    let mut removed = old.to_vec();
+    let mut added = Vec::new();
+
+    new.iter()
+        .for_each(|card| match removed.iter().position(|c| c == card) {
+            Some(index) => {
+                removed.remove(index);
+            }
+            None => added.push(card.clone()),
+        });
+
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn diff_counts_duplicates() {
+        let id = Uuid::nil();
+        let (seven, two) = (Card::new("Seven", "Red", id), Card::new("Two", "Blue", id));
+
+        let old = vec![seven.clone(), seven.clone(), two.clone()];
+        let new = vec![seven.clone(), two.clone(), two.clone()];
+
+        assert_eq!(diff_hand(&old, &new), (vec![two], vec![seven]));
+    }
+
+    #[test]
+    fn staggered_animations_finish_in_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.push(vec![None, None], Point::DECK, Point::HAND, 0.0);
+
+        assert_eq!(scheduler.animations[1].start, STAGGER);
+        assert!(scheduler.tick(DURATION));
+        assert_eq!(scheduler.animations.len(), 1);
+        assert_eq!(
+            scheduler.in_flight().next().unwrap().1,
+            scheduler.animations[0].position(DURATION).unwrap()
+        );
+        assert!(!scheduler.tick(DURATION + STAGGER));
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        let animation = Animation {
+            card: None,
+            from: Point::DECK,
+            to: Point::PILE,
+            start: 100.0,
+        };
+
+        assert_eq!(animation.progress(50.0), None);
+        assert_eq!(animation.progress(100.0), Some(0.0));
+        assert_eq!(animation.position(1000.0), Some(Point::PILE));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::settings::Settings;
+use wasm_bindgen::JsValue;
+use web_sys::{AudioContext, OscillatorType};
+use yew::services::ConsoleService;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cue {
+    Turn,
+    CardPlaced,
+    CardDrawn,
+    Uno,
+    Chat,
+    Mention,
+    MatchFound,
+    Win,
+}
+
+impl Cue {
+    // Synthesized as a sequence of (frequency in Hz, duration in seconds)
+    fn notes(&self) -> &'static [(f32, f64)] {
+        match self {
+            Cue::Turn => &[(523.25, 0.08), (783.99, 0.12)],
+            Cue::CardPlaced => &[(329.63, 0.06)],
+            Cue::CardDrawn => &[(220.0, 0.05), (246.94, 0.05)],
+            Cue::Uno => &[(659.25, 0.1), (659.25, 0.1), (987.77, 0.2)],
+            Cue::Chat => &[(880.0, 0.05)],
+            Cue::Mention => &[(880.0, 0.06), (1174.66, 0.1)],
+            Cue::MatchFound => &[(587.33, 0.08), (783.99, 0.08), (1174.66, 0.16)],
+            Cue::Win => &[
+                (523.25, 0.12),
+                (659.25, 0.12),
+                (783.99, 0.12),
+                (1046.5, 0.3),
+            ],
+        }
+    }
+}
+
+// Sound cues synthesized with the Web Audio API, so no audio assets are needed
+pub struct Audio {
+    context: Option<AudioContext>,
+}
+
+impl Audio {
+    pub fn new() -> Audio {
+        Audio { context: None }
+    }
+
+    pub fn play(&mut self, cue: Cue, settings: &Settings) {
+        if settings.muted || settings.volume <= 0.0 {
+            return;
+        }
+
+        if let Err(e) = self.schedule(cue, settings.volume as f32) {
+            ConsoleService::error(&format!("Failed to play a sound: {:?}", e));
+        }
+    }
+
+    fn schedule(&mut self, cue: Cue, volume: f32) -> Result<(), JsValue> {
+        // Browsers only allow audio after a user gesture, the context is created on first use
+        if self.context.is_none() {
+            self.context = Some(AudioContext::new()?);
+        }
+        let context = self.context.as_ref().unwrap();
+        let _ = context.resume()?;
+
+        let mut time = context.current_time();
+        for (frequency, duration) in cue.notes() {
+            let oscillator = context.create_oscillator()?;
+            let gain = context.create_gain()?;
+
+            oscillator.set_type(OscillatorType::Triangle);
+            oscillator.frequency().set_value(*frequency);
+            gain.gain().set_value_at_time(volume * 0.3, time)?;
+            gain.gain()
+                .exponential_ramp_to_value_at_time(0.001, time + duration)?;
+
+            oscillator.connect_with_audio_node(&gain)?;
+            gain.connect_with_audio_node(&context.destination())?;
+
+            oscillator.start_with_when(time)?;
+            oscillator.stop_with_when(time + duration)?;
+            time += duration;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Audio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,141 @@
+use crate::game::Card;
+use yew::prelude::*;
+
+const WILD: &str = "#222222";
+const COLORS: [&str; 4] = ["Red", "Yellow", "Green", "Blue"];
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CardFaceProps {
+    pub card: Card,
+    pub colors: [String; 4], // red, yellow, green, blue
+}
+
+// Any card drawn as inline SVG from its type and color, so no per-card assets are needed.
+// Colors outside the four card colors render as a wild card.
+pub struct CardFace {
+    props: CardFaceProps,
+}
+
+pub fn symbol(r#type: &str) -> &'static str {
+    match r#type {
+        "Zero" => "0",
+        "One" => "1",
+        "Two" => "2",
+        "Three" => "3",
+        "Four" => "4",
+        "Five" => "5",
+        "Six" => "6",
+        "Seven" => "7",
+        "Eight" => "8",
+        "Nine" => "9",
+        "Block" => "⊘",
+        "Reverse" => "⇄",
+        "DrawTwo" => "+2",
+        "DrawFour" => "+4",
+        "Switch" => "W",
+        _ => "",
+    }
+}
+
+pub fn fill<'a>(color: &str, colors: &'a [String; 4]) -> &'a str {
+    match COLORS.iter().position(|c| c == &color) {
+        Some(index) => &colors[index],
+        None => WILD,
+    }
+}
+
+impl Component for CardFace {
+    type Message = ();
+    type Properties = CardFaceProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            self.props = props;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn view(&self) -> Html {
+        let card = &self.props.card;
+        let fill = fill(&card.color, &self.props.colors).to_string();
+        let symbol = symbol(&card.r#type);
+        let wild = card.r#type == "Switch" || card.r#type == "DrawFour";
+
+        html! {
+            <svg class="card-face" viewBox="0 0 120 185" xmlns="http://www.w3.org/2000/svg">
+                <rect x="0" y="0" width="120" height="185" rx="10" fill="white"/>
+                <rect x="7" y="7" width="106" height="171" rx="8" fill=fill.clone()/>
+                <ellipse cx="60" cy="92.5" rx="40" ry="68" transform="rotate(25 60 92.5)" fill="white"/>
+                {
+                    if wild {
+                        // Four colored quarters, the wild card's color wheel
+                        html! {
+                            <g transform="rotate(25 60 92.5)">
+                            {
+                                for COLORS.iter().enumerate().map(|(i, color)| {
+                                    let (x, y) = (36 + (i % 2) * 24, 68 + (i / 2) * 24);
+                                    html! {
+                                        <rect x=x.to_string() y=y.to_string() width="24" height="24" fill=self::fill(color, &self.props.colors).to_string()/>
+                                    }
+                                })
+                            }
+                            </g>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <text x="60" y="92.5" text-anchor="middle" dominant-baseline="central"
+                    font-size={if symbol.chars().count() > 1 {"38"} else {"52"}} font-weight="bold"
+                    font-family="Arial, sans-serif"
+                    fill={if wild {"white".to_string()} else {fill.clone()}} stroke="black" stroke-width="1.5">
+                    {if card.r#type == "Switch" {""} else {symbol}}
+                </text>
+                <text x="17" y="27" text-anchor="middle" dominant-baseline="central"
+                    font-size="16" font-weight="bold" font-family="Arial, sans-serif" fill="white">
+                    {symbol}
+                </text>
+                <text x="103" y="158" text-anchor="middle" dominant-baseline="central" transform="rotate(180 103 158)"
+                    font-size="16" font-weight="bold" font-family="Arial, sans-serif" fill="white">
+                    {symbol}
+                </text>
+            </svg>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::Palette;
+
+    #[test]
+    fn unknown_colors_render_as_wild() {
+        let colors = |palette: Palette| palette.colors().map(String::from);
+
+        assert_eq!(fill("Green", &colors(Palette::Default)), "#55AA55");
+        assert_eq!(fill("Green", &colors(Palette::Deuteranopia)), "#009E73");
+        assert_eq!(fill("Black", &colors(Palette::Default)), WILD);
+        assert_eq!(fill("", &colors(Palette::Default)), WILD);
+    }
+
+    #[test]
+    fn every_type_has_a_symbol() {
+        [
+            "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine",
+            "Block", "Reverse", "DrawTwo", "DrawFour", "Switch",
+        ]
+        .iter()
+        .for_each(|t| assert!(!symbol(t).is_empty(), "{} has no symbol", t));
+    }
+}
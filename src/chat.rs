@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+pub const MAX_ENTRIES: usize = 500; // older messages are dropped
+pub const PAGE: usize = 50; // messages rendered at once, scrolling to the top renders more
+pub const REACTIONS: [&str; 5] = ["👍", "😂", "😮", "❤️", "🎉"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Room,
+    Spectators,    // only seen by spectators, so they can't tell players about hands
+    Whisper(Uuid), // direct messages with the player
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerMessage {
+    Join(String),
+    Leave(String),
+    Message(String, String),
+    Warning(String),
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub id: Option<Uuid>, // assigned by the server to messages players wrote
+    pub time: OffsetDateTime,
+    pub message: ServerMessage,
+    pub channel: Channel,
+    pub reactions: Vec<(String, HashSet<Uuid>)>, // emoji, who reacted
+    pub mention: bool,
+}
+
+pub struct Chat {
+    pub entries: VecDeque<Entry>,
+    pub channel: Channel, // selected tab
+    pub window: usize,    // how many of the latest entries are rendered
+    pub pinned: bool,     // scrolled to the bottom, new messages keep it there
+    pub unread: HashMap<Channel, usize>,
+}
+
+impl Chat {
+    pub fn new() -> Chat {
+        Chat {
+            entries: VecDeque::new(),
+            channel: Channel::Room,
+            window: PAGE,
+            pinned: true,
+            unread: HashMap::new(),
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        id: Option<Uuid>,
+        message: ServerMessage,
+        channel: Channel,
+        time: OffsetDateTime,
+        mention: bool,
+    ) {
+        self.entries.push_back(Entry {
+            id,
+            time,
+            message,
+            channel,
+            reactions: Vec::new(),
+            mention,
+        });
+
+        while self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    fn in_channel(&self) -> impl Iterator<Item = &Entry> {
+        let channel = self.channel;
+        self.entries.iter().filter(move |e| e.channel == channel)
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &Entry> {
+        let count = self.in_channel().count();
+        self.in_channel().skip(count.saturating_sub(self.window))
+    }
+
+    pub fn has_older(&self) -> bool {
+        self.in_channel().count() > self.window
+    }
+
+    // Room first, then the others in the order they were first written to
+    pub fn channels(&self) -> Vec<Channel> {
+        let mut channels = vec![Channel::Room];
+        self.entries
+            .iter()
+            .map(|e| e.channel)
+            .chain(std::iter::once(self.channel))
+            .for_each(|channel| {
+                if !channels.contains(&channel) {
+                    channels.push(channel);
+                }
+            });
+
+        channels
+    }
+
+    pub fn select(&mut self, channel: Channel) {
+        self.channel = channel;
+        self.pin();
+    }
+
+    pub fn unread(&self, channel: Channel) -> usize {
+        self.unread.get(&channel).copied().unwrap_or(0)
+    }
+
+    pub fn unread_total(&self) -> usize {
+        self.unread.values().sum()
+    }
+
+    pub fn load_older(&mut self) {
+        self.window = (self.window + PAGE).min(MAX_ENTRIES);
+    }
+
+    // Back at the bottom, everything has been seen and the older messages can be let go from the page
+    pub fn pin(&mut self) {
+        self.pinned = true;
+        self.window = PAGE;
+        self.unread.remove(&self.channel);
+    }
+
+    // Reactions are sent to the whole room, so messages in other channels can't have them
+    pub fn reactable(&self, message: Uuid) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|e| e.channel == Channel::Room && e.id == Some(message))
+    }
+
+    // Applies a reaction to the room message, returns whether it was found
+    pub fn react(&mut self, id: Uuid, message: Uuid, emoji: &str, added: bool) -> bool {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.channel == Channel::Room && e.id == Some(message));
+
+        let entry = match entry {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        match entry.reactions.iter().position(|(e, _)| e == emoji) {
+            Some(index) => {
+                let reactors = &mut entry.reactions[index].1;
+                if added {
+                    reactors.insert(id);
+                } else {
+                    reactors.remove(&id);
+                }
+                if reactors.is_empty() {
+                    entry.reactions.remove(index);
+                }
+            }
+            None if added => entry.reactions.push((emoji.to_string(), [id].into())),
+            None => {}
+        }
+
+        true
+    }
+}
+
+impl Default for Chat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Whether the content mentions the username as "@username", ignoring case
+pub fn mentions(content: &str, username: &str) -> bool {
+    !username.is_empty()
+        && segments(content)
+            .iter()
+            .any(|(text, mention)| *mention && text[1..].eq_ignore_ascii_case(username))
+}
+
+// Splits the content into plain text and @mentions, so mentions can be highlighted
+pub fn segments(content: &str) -> Vec<(String, bool)> {
+    let mut segments: Vec<(String, bool)> = Vec::new();
+    let mut chars = content.chars().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        let starts_mention = c == '@'
+            && previous.is_none_or(char::is_whitespace)
+            && chars.peek().is_some_and(|n| is_name_char(*n));
+
+        if starts_mention {
+            let mut mention = String::from('@');
+            while let Some(n) = chars.peek().copied().filter(|n| is_name_char(*n)) {
+                mention.push(n);
+                chars.next();
+            }
+            previous = mention.chars().last();
+            segments.push((mention, true));
+            continue;
+        }
+
+        match segments.last_mut() {
+            Some((text, false)) => text.push(c),
+            _ => segments.push((c.to_string(), false)),
+        }
+        previous = Some(c);
+    }
+
+    segments
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+// Milliseconds since the unix epoch and the offset of the local time zone as minutes behind UTC,
+// as given by javascript's Date
+pub fn local_time(millis: f64, offset: f64) -> OffsetDateTime {
+    let time = OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000)
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+    let offset = UtcOffset::from_whole_seconds(-(offset as i32) * 60).unwrap_or(UtcOffset::UTC);
+
+    time.to_offset(offset)
+}
+
+pub fn format_time(time: &OffsetDateTime) -> String {
+    format!("{:02}:{:02}", time.hour(), time.minute())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_mentions() {
+        assert_eq!(
+            segments("hi @Bob, it's @you"),
+            vec![
+                ("hi ".to_string(), false),
+                ("@Bob".to_string(), true),
+                (", it's ".to_string(), false),
+                ("@you".to_string(), true),
+            ]
+        );
+        assert!(mentions("hi @bob!", "Bob"));
+        assert!(!mentions("hi @bobby", "bob"));
+        assert!(!mentions("mail bob@example.com", "example"));
+    }
+
+    #[test]
+    fn scrollback_is_bounded() {
+        let mut chat = Chat::new();
+        (0..MAX_ENTRIES + 10).for_each(|i| {
+            chat.push(
+                None,
+                ServerMessage::Message("bob".to_string(), i.to_string()),
+                Channel::Room,
+                OffsetDateTime::UNIX_EPOCH,
+                false,
+            )
+        });
+
+        assert_eq!(chat.entries.len(), MAX_ENTRIES);
+        assert_eq!(chat.visible().count(), PAGE);
+        assert_eq!(
+            chat.visible().last().unwrap().message,
+            ServerMessage::Message("bob".to_string(), (MAX_ENTRIES + 9).to_string())
+        );
+
+        chat.load_older();
+        assert_eq!(chat.visible().count(), 2 * PAGE);
+    }
+
+    #[test]
+    fn reactions_are_idempotent() {
+        let (a, b, message) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut chat = Chat::new();
+        chat.push(
+            Some(message),
+            ServerMessage::Message("bob".to_string(), "gg".to_string()),
+            Channel::Room,
+            OffsetDateTime::UNIX_EPOCH,
+            false,
+        );
+
+        assert!(chat.react(a, message, "🎉", true));
+        assert!(chat.react(a, message, "🎉", true));
+        assert!(chat.react(b, message, "🎉", true));
+        assert_eq!(chat.entries[0].reactions[0].1.len(), 2);
+
+        chat.react(a, message, "🎉", false);
+        chat.react(b, message, "🎉", false);
+        assert!(chat.entries[0].reactions.is_empty());
+        assert!(!chat.react(a, Uuid::new_v4(), "🎉", true));
+    }
+
+    #[test]
+    fn reactions_follow_the_message_id() {
+        let (a, first, second) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut chat = Chat::new();
+        let message = || ServerMessage::Message("bob".to_string(), "gg".to_string());
+
+        // The same text twice, only the message reacted to gets the reaction
+        chat.push(
+            Some(first),
+            message(),
+            Channel::Room,
+            OffsetDateTime::UNIX_EPOCH,
+            false,
+        );
+        chat.push(
+            Some(second),
+            message(),
+            Channel::Room,
+            OffsetDateTime::UNIX_EPOCH,
+            false,
+        );
+        assert!(chat.react(a, first, "🎉", true));
+        assert_eq!(chat.entries[0].reactions.len(), 1);
+        assert!(chat.entries[1].reactions.is_empty());
+    }
+
+    #[test]
+    fn reactions_stay_in_the_room() {
+        let (a, bob, message) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut chat = Chat::new();
+
+        chat.push(
+            Some(message),
+            ServerMessage::Message("bob".to_string(), "gg".to_string()),
+            Channel::Whisper(bob),
+            OffsetDateTime::UNIX_EPOCH,
+            false,
+        );
+        assert!(chat.reactable(message).is_none());
+        assert!(!chat.react(a, message, "🎉", true));
+        assert!(chat.entries[0].reactions.is_empty());
+    }
+
+    #[test]
+    fn messages_are_kept_apart_by_channel() {
+        let bob = Uuid::new_v4();
+        let mut chat = Chat::new();
+        let message =
+            |content: &str| ServerMessage::Message("bob".to_string(), content.to_string());
+
+        chat.push(
+            None,
+            message("hi all"),
+            Channel::Room,
+            OffsetDateTime::UNIX_EPOCH,
+            false,
+        );
+        chat.push(
+            None,
+            message("psst"),
+            Channel::Whisper(bob),
+            OffsetDateTime::UNIX_EPOCH,
+            false,
+        );
+
+        assert_eq!(chat.channels(), vec![Channel::Room, Channel::Whisper(bob)]);
+        assert_eq!(chat.visible().count(), 1);
+
+        chat.select(Channel::Whisper(bob));
+        assert_eq!(chat.visible().next().unwrap().message, message("psst"));
+    }
+
+    #[test]
+    fn local_time_uses_offset() {
+        // 2022-01-01 12:30 UTC, two hours ahead of UTC
+        let time = local_time(1_641_040_200_000.0, -120.0);
+        assert_eq!(format_time(&time), "14:30");
+    }
+}
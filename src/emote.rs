@@ -0,0 +1,38 @@
+use crate::i18n::{self, Language};
+use serde::{Deserialize, Serialize};
+
+pub const EMOTE_LIMIT: (usize, f64) = (3, 4000.0); // emotes allowed inside the window, ms
+pub const BUBBLE_DURATION: u64 = 3000; // ms a speech bubble stays over the player
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Emote {
+    Nice,
+    Oops,
+    GoodGame,
+    Laugh,
+    Shock,
+    Angry,
+}
+
+impl Emote {
+    pub const ALL: [Emote; 6] = [
+        Emote::Nice,
+        Emote::Oops,
+        Emote::GoodGame,
+        Emote::Laugh,
+        Emote::Shock,
+        Emote::Angry,
+    ];
+
+    // Emoji read the same in every language, the rest are translated
+    pub fn text(&self, language: Language) -> String {
+        match self {
+            Emote::Nice => i18n::tr(language, "emote-nice"),
+            Emote::Oops => i18n::tr(language, "emote-oops"),
+            Emote::GoodGame => i18n::tr(language, "emote-good-game"),
+            Emote::Laugh => "😂".to_string(),
+            Emote::Shock => "😱".to_string(),
+            Emote::Angry => "😠".to_string(),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use yew::format::Json;
+use yew::services::storage::StorageService;
+
+const KEY: &str = "uno.friends";
+pub const PRESENCE_PATH: &str = "presence";
+pub const MAX_INVITES: usize = 5; // older invites are dropped
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Presence {
+    Offline,
+    Online,         // connected, but not in a room
+    InRoom(String), // room id
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Friend {
+    pub identity: Uuid,
+    pub username: String, // as last seen
+}
+
+// Invitation to join a friend's room, waiting for the player to answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invite {
+    pub identity: Uuid,
+    pub username: String,
+    pub room: String,
+}
+
+// Stored in local storage, presence is only known while the presence connection is open
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Friends {
+    pub friends: Vec<Friend>,
+    #[serde(skip)]
+    pub presence: HashMap<Uuid, Presence>,
+    #[serde(skip)]
+    pub invites: Vec<Invite>,
+}
+
+impl Friends {
+    pub fn load(storage: &Option<StorageService>) -> Friends {
+        match storage {
+            Some(storage) => {
+                let Json(friends) = storage.restore(KEY);
+                friends.unwrap_or_default()
+            }
+            None => Friends::default(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut Option<StorageService>) {
+        if let Some(storage) = storage {
+            storage.store(KEY, Json(self));
+        }
+    }
+
+    pub fn identities(&self) -> Vec<Uuid> {
+        self.friends.iter().map(|f| f.identity).collect()
+    }
+
+    pub fn contains(&self, identity: &Uuid) -> bool {
+        self.friends.iter().any(|f| &f.identity == identity)
+    }
+
+    // Adding a friend again only updates the name they're shown with
+    pub fn add(&mut self, identity: Uuid, username: String) {
+        match self.friends.iter_mut().find(|f| f.identity == identity) {
+            Some(friend) => friend.username = username,
+            None => self.friends.push(Friend { identity, username }),
+        }
+    }
+
+    pub fn remove(&mut self, identity: &Uuid) {
+        self.friends.retain(|f| &f.identity != identity);
+        self.presence.remove(identity);
+        self.invites.retain(|i| &i.identity != identity);
+    }
+
+    pub fn presence(&self, identity: &Uuid) -> Presence {
+        self.presence
+            .get(identity)
+            .cloned()
+            .unwrap_or(Presence::Offline)
+    }
+
+    // Online friends first, then by name
+    pub fn sorted(&self) -> Vec<&Friend> {
+        let mut friends: Vec<&Friend> = self.friends.iter().collect();
+        friends.sort_by_key(|f| {
+            (
+                self.presence(&f.identity) == Presence::Offline,
+                f.username.to_lowercase(),
+            )
+        });
+        friends
+    }
+
+    // Only friends can invite, a newer invite from the same friend replaces the older one.
+    // Returns whether the invite was kept.
+    pub fn invite(&mut self, invite: Invite) -> bool {
+        if !self.contains(&invite.identity) {
+            return false;
+        }
+
+        self.invites.retain(|i| i.identity != invite.identity);
+        self.invites.push(invite);
+        if self.invites.len() > MAX_INVITES {
+            self.invites.remove(0);
+        }
+        true
+    }
+
+    pub fn take_invite(&mut self, identity: &Uuid) -> Option<Invite> {
+        let index = self.invites.iter().position(|i| &i.identity == identity)?;
+        Some(self.invites.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invite(identity: Uuid, room: &str) -> Invite {
+        Invite {
+            identity,
+            username: "bob".to_string(),
+            room: room.to_string(),
+        }
+    }
+
+    #[test]
+    fn only_friends_can_invite() {
+        let (bob, eve) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut friends = Friends::default();
+        friends.add(bob, "bob".to_string());
+        friends.add(bob, "bobby".to_string());
+        assert_eq!(friends.friends.len(), 1);
+
+        assert!(!friends.invite(invite(eve, "a")));
+        assert!(friends.invite(invite(bob, "a")));
+        assert!(friends.invite(invite(bob, "b")));
+        assert_eq!(friends.invites.len(), 1);
+        assert_eq!(friends.take_invite(&bob).unwrap().room, "b");
+        assert_eq!(friends.take_invite(&bob), None);
+    }
+
+    #[test]
+    fn online_friends_come_first() {
+        let (ada, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut friends = Friends::default();
+        friends.add(ada, "ada".to_string());
+        friends.add(bob, "bob".to_string());
+        friends
+            .presence
+            .insert(bob, Presence::InRoom("room".to_string()));
+
+        let sorted: Vec<&str> = friends
+            .sorted()
+            .iter()
+            .map(|f| f.username.as_str())
+            .collect();
+        assert_eq!(sorted, vec!["bob", "ada"]);
+
+        // Presence and invites aren't stored
+        let json = serde_json::to_string(&friends).unwrap();
+        let restored: Friends = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.presence(&bob), Presence::Offline);
+        assert_eq!(restored.friends, friends.friends);
+    }
+}
@@ -1,5 +1,9 @@
-use std::{collections::VecDeque, time::SystemTime};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::SystemTime,
+};
 
+use crate::profile::Profile;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -18,6 +22,46 @@ impl Card {
             owner: Some(owner),
         }
     }
+
+    // Human readable name, e.g. "Red Draw Two"
+    pub fn display_name(&self) -> String {
+        let mut r#type = String::new();
+
+        self.r#type.chars().enumerate().for_each(|(i, c)| {
+            if i > 0 && c.is_uppercase() {
+                r#type.push(' ');
+            }
+            r#type.push(c);
+        });
+
+        format!("{} {}", self.color, r#type)
+    }
+
+    pub fn is_special(&self) -> bool {
+        matches!(
+            self.r#type.as_str(),
+            "Block" | "Reverse" | "DrawTwo" | "DrawFour" | "Switch"
+        )
+    }
+
+    // Standard UNO scoring: face value for numbers, 20 for action cards and 50 for wild cards
+    pub fn points(&self) -> usize {
+        match self.r#type.as_str() {
+            "Zero" => 0,
+            "One" => 1,
+            "Two" => 2,
+            "Three" => 3,
+            "Four" => 4,
+            "Five" => 5,
+            "Six" => 6,
+            "Seven" => 7,
+            "Eight" => 8,
+            "Nine" => 9,
+            "Block" | "Reverse" | "DrawTwo" => 20,
+            "Switch" | "DrawFour" => 50,
+            _ => 0,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,6 +71,12 @@ pub struct Player {
     pub index: usize,
     pub turn: bool,
     pub next: bool,
+    #[serde(default)]
+    pub profile: Profile,
+    #[serde(default)]
+    pub identity: Option<Uuid>, // persistent, known once the server has checked it
+    #[serde(default)]
+    pub account: Option<String>,
 }
 
 impl Player {
@@ -37,6 +87,9 @@ impl Player {
             index,
             turn: false,
             next: false,
+            profile: Profile::default(),
+            identity: None,
+            account: None,
         }
     }
 }
@@ -49,6 +102,12 @@ pub struct GameStatistics {
     pub spectator_count: usize,
     pub cards_placed: usize,
     pub cards_drawn: usize,
+    #[serde(default)]
+    pub players: Vec<PlayerStatistics>,
+    #[serde(default)]
+    pub hands: Vec<(Uuid, Vec<Card>)>, // id, cards left in hand when the round ended
+    #[serde(default)]
+    pub points: Option<usize>, // earned by the winner, when the server counts them itself
 }
 
 impl GameStatistics {
@@ -60,6 +119,9 @@ impl GameStatistics {
             spectator_count: 0,
             cards_placed: 0,
             cards_drawn: 0,
+            players: Vec::new(),
+            hands: Vec::new(),
+            points: None,
         }
     }
 }
@@ -70,6 +132,42 @@ impl Default for GameStatistics {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerStatistics {
+    pub id: Uuid,
+    pub username: String,
+    pub cards_placed: usize,
+    pub cards_drawn: usize,
+    pub specials_played: usize,
+    pub longest_streak: usize,
+    pub turns_taken: usize,
+    pub turn_time: u64, // total milliseconds spent on turns
+}
+
+impl PlayerStatistics {
+    pub fn new(id: Uuid, username: String) -> PlayerStatistics {
+        PlayerStatistics {
+            id,
+            username,
+            cards_placed: 0,
+            cards_drawn: 0,
+            specials_played: 0,
+            longest_streak: 0,
+            turns_taken: 0,
+            turn_time: 0,
+        }
+    }
+
+    // Average turn time in seconds
+    pub fn average_turn_time(&self) -> f64 {
+        if self.turns_taken == 0 {
+            0.0
+        } else {
+            self.turn_time as f64 / self.turns_taken as f64 / 1000.0
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EndStatus {
     pub winner_id: Uuid,
@@ -77,3 +175,269 @@ pub struct EndStatus {
     pub placements: VecDeque<String>,
     pub statistics: GameStatistics,
 }
+
+// Votes for another round after one has ended. Everyone still in the room has to agree,
+// the votes of players who left don't count.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RematchVotes {
+    votes: HashSet<Uuid>,
+}
+
+impl RematchVotes {
+    pub fn vote(&mut self, id: Uuid, ready: bool) {
+        if ready {
+            self.votes.insert(id);
+        } else {
+            self.votes.remove(&id);
+        }
+    }
+
+    pub fn leave(&mut self, id: &Uuid) {
+        self.votes.remove(id);
+    }
+
+    pub fn clear(&mut self) {
+        self.votes.clear();
+    }
+
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.votes.contains(id)
+    }
+
+    pub fn count(&self, players: &[Uuid]) -> usize {
+        players.iter().filter(|id| self.votes.contains(id)).count()
+    }
+
+    pub fn ready(&self, players: &[Uuid]) -> bool {
+        players.len() > 1 && self.count(players) == players.len()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameOptions {
+    pub score_target: Option<usize>, // None plays a single round
+}
+
+impl GameOptions {
+    pub fn new() -> GameOptions {
+        GameOptions { score_target: None }
+    }
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Seat {
+    pub id: Uuid,
+    pub username: String,
+    pub card_count: usize,
+}
+
+// Authoritative state of the room as seen by one client
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StateSnapshot {
+    pub seats: Vec<Seat>, // in play order, including the receiving client
+    pub active: bool,
+    pub current: Option<Card>,
+    pub color: Option<String>,
+    pub direction: Direction,
+    pub turn: Option<Uuid>,
+    pub next: Option<Uuid>,
+    pub cards: Vec<Card>,         // the receiving client's hand
+    pub allowed_cards: Vec<Card>, // empty unless it's the receiving client's turn
+    pub options: GameOptions,
+    #[serde(default)]
+    pub spectators: Vec<Seat>, // joined after the game started, may include the receiving client
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Score {
+    pub target: usize,
+    pub rounds: usize,
+    pub points: HashMap<Uuid, usize>,
+}
+
+impl Score {
+    pub const DEFAULT_TARGET: usize = 500;
+
+    pub fn new(target: usize) -> Score {
+        Score {
+            target,
+            rounds: 0,
+            points: HashMap::new(),
+        }
+    }
+
+    pub fn hand_value(cards: &[Card]) -> usize {
+        cards.iter().map(Card::points).sum()
+    }
+
+    // The winner of a round earns the value of every card left in the opponents' hands.
+    // Returns the amount of points earned.
+    pub fn award(&mut self, winner: Uuid, hands: &[(Uuid, Vec<Card>)]) -> usize {
+        let earned = hands
+            .iter()
+            .filter(|(id, _)| id != &winner)
+            .map(|(_, cards)| Score::hand_value(cards))
+            .sum();
+
+        self.credit(winner, earned)
+    }
+
+    // Points counted by the server
+    pub fn credit(&mut self, winner: Uuid, earned: usize) -> usize {
+        *self.points.entry(winner).or_insert(0) += earned;
+        self.rounds += 1;
+
+        earned
+    }
+
+    // A round without the opponents' hands still counts, but nobody can tell what it was worth
+    pub fn skip(&mut self) {
+        self.rounds += 1;
+    }
+
+    pub fn get(&self, id: &Uuid) -> usize {
+        *self.points.get(id).unwrap_or(&0)
+    }
+
+    // Only one player can reach the target, as points are only awarded to the round winner
+    pub fn winner(&self) -> Option<Uuid> {
+        self.points
+            .iter()
+            .find(|(_, points)| **points >= self.target)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.winner().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(r#type: &str) -> Card {
+        Card::new(r#type, "Red", Uuid::nil())
+    }
+
+    #[test]
+    fn card_points() {
+        assert_eq!(card("Zero").points(), 0);
+        assert_eq!(card("Seven").points(), 7);
+        assert_eq!(card("Reverse").points(), 20);
+        assert_eq!(card("Block").points(), 20);
+        assert_eq!(card("DrawTwo").points(), 20);
+        assert_eq!(card("Switch").points(), 50);
+        assert_eq!(card("DrawFour").points(), 50);
+    }
+
+    #[test]
+    fn hand_value() {
+        let hand = vec![card("Nine"), card("DrawTwo"), card("DrawFour")];
+
+        assert_eq!(Score::hand_value(&hand), 79);
+        assert_eq!(Score::hand_value(&[]), 0);
+    }
+
+    #[test]
+    fn award_ignores_winner_hand() {
+        let (winner, a, b) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut score = Score::new(Score::DEFAULT_TARGET);
+
+        let earned = score.award(
+            winner,
+            &[
+                (winner, vec![card("Switch")]),
+                (a, vec![card("Three"), card("Reverse")]),
+                (b, vec![card("Five")]),
+            ],
+        );
+
+        assert_eq!(earned, 28);
+        assert_eq!(score.get(&winner), 28);
+        assert_eq!(score.get(&a), 0);
+        assert_eq!(score.rounds, 1);
+    }
+
+    #[test]
+    fn points_accumulate_until_target() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut score = Score::new(100);
+
+        score.award(a, &[(b, vec![card("DrawFour")])]);
+        assert!(!score.is_finished());
+
+        score.award(b, &[(a, vec![card("Nine")])]);
+        score.award(a, &[(b, vec![card("Switch")])]);
+
+        assert_eq!(score.get(&a), 100);
+        assert_eq!(score.get(&b), 9);
+        assert_eq!(score.rounds, 3);
+        assert_eq!(score.winner(), Some(a));
+    }
+
+    #[test]
+    fn counts_rematch_votes() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let players = [a, b, c];
+        let mut votes = RematchVotes::default();
+
+        votes.vote(a, true);
+        votes.vote(b, true);
+        votes.vote(b, true);
+        assert_eq!(votes.count(&players), 2);
+        assert!(!votes.ready(&players));
+
+        votes.vote(b, false);
+        votes.vote(c, true);
+        assert_eq!(votes.count(&players), 2);
+
+        votes.vote(b, true);
+        assert!(votes.ready(&players));
+        assert!(!votes.ready(&[a]));
+    }
+
+    #[test]
+    fn rematch_votes_of_leavers_dont_count() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut votes = RematchVotes::default();
+        votes.vote(a, true);
+        votes.vote(c, true);
+
+        // c left, the two still in the room decide
+        votes.leave(&c);
+        assert!(!votes.contains(&c));
+        assert_eq!(votes.count(&[a, b]), 1);
+
+        votes.vote(b, true);
+        assert!(votes.ready(&[a, b]));
+
+        // A vote from someone no longer listed doesn't count either
+        votes.vote(c, true);
+        assert_eq!(votes.count(&[a, b]), 2);
+    }
+
+    #[test]
+    fn unscored_rounds_award_nothing() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut score = Score::new(100);
+
+        assert_eq!(score.credit(a, 40), 40);
+        score.skip();
+
+        assert_eq!((score.get(&a), score.get(&b)), (40, 0));
+        assert_eq!(score.rounds, 2);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::game::Card;
+use crate::i18n::{self, Language};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    Placed(String, Card),        // username, card
+    Drew(String, usize),         // username, amount
+    Turn(String),                // username
+    ColorSwitch(String, String), // username, color
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventFilter {
+    All,
+    Placed,
+    Drew,
+    Turn,
+    ColorSwitch,
+}
+
+impl EventFilter {
+    pub const ALL: [EventFilter; 5] = [
+        EventFilter::All,
+        EventFilter::Placed,
+        EventFilter::Drew,
+        EventFilter::Turn,
+        EventFilter::ColorSwitch,
+    ];
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            EventFilter::All => "log-all",
+            EventFilter::Placed => "log-plays",
+            EventFilter::Drew => "log-draws",
+            EventFilter::Turn => "log-turns",
+            EventFilter::ColorSwitch => "log-colors",
+        }
+    }
+}
+
+impl GameEvent {
+    // Derives the event from the change in a player's hand size.
+    // A player holding 0 cards before the update is being dealt their hand, which is not logged.
+    pub fn from_status_update(
+        username: &str,
+        previous: usize,
+        card_count: usize,
+        current: &Card,
+    ) -> Option<GameEvent> {
+        if previous == 0 || card_count == previous {
+            None
+        } else if card_count < previous {
+            Some(GameEvent::Placed(username.to_string(), current.clone()))
+        } else {
+            Some(GameEvent::Drew(username.to_string(), card_count - previous))
+        }
+    }
+
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            GameEvent::Placed(username, card) => i18n::format(
+                language,
+                "event-placed",
+                &[
+                    ("username", username.as_str().into()),
+                    ("card", i18n::card_name(language, card).into()),
+                ],
+            ),
+            GameEvent::Drew(username, amount) => i18n::format(
+                language,
+                "event-drew",
+                &[
+                    ("username", username.as_str().into()),
+                    ("n", (*amount).into()),
+                ],
+            ),
+            GameEvent::Turn(username) => i18n::format(
+                language,
+                "event-turn",
+                &[("username", username.as_str().into())],
+            ),
+            GameEvent::ColorSwitch(username, color) => i18n::format(
+                language,
+                "event-color",
+                &[
+                    ("username", username.as_str().into()),
+                    ("color", i18n::color_name(language, color).into()),
+                ],
+            ),
+        }
+    }
+
+    pub fn matches(&self, filter: EventFilter) -> bool {
+        matches!(
+            (filter, self),
+            (EventFilter::All, _)
+                | (EventFilter::Placed, GameEvent::Placed(..))
+                | (EventFilter::Drew, GameEvent::Drew(..))
+                | (EventFilter::Turn, GameEvent::Turn(..))
+                | (EventFilter::ColorSwitch, GameEvent::ColorSwitch(..))
+        )
+    }
+}
+
+pub struct GameLog {
+    pub events: Vec<GameEvent>,
+    pub filter: EventFilter,
+}
+
+impl GameLog {
+    pub fn new() -> GameLog {
+        GameLog {
+            events: Vec::new(),
+            filter: EventFilter::All,
+        }
+    }
+
+    pub fn push(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
+    pub fn filtered(&self) -> impl DoubleEndedIterator<Item = (usize, &GameEvent)> {
+        let filter = self.filter;
+
+        self.events
+            .iter()
+            .enumerate()
+            .filter(move |(_, event)| event.matches(filter))
+    }
+
+    pub fn to_text(&self, language: Language) -> String {
+        self.filtered()
+            .map(|(i, event)| format!("{}. {}\n", i + 1, event.describe(language)))
+            .collect()
+    }
+
+    pub fn to_data_uri(&self, language: Language) -> String {
+        data_uri("text/plain", &self.to_text(language))
+    }
+}
+
+impl Default for GameLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Content as a data URI, so it can be downloaded through a plain <a download> link
+pub fn data_uri(mime: &str, content: &str) -> String {
+    format!(
+        "data:{};charset=utf-8,{}",
+        mime,
+        encode_uri_component(content)
+    )
+}
+
+pub fn encode_uri_component(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn log() -> GameLog {
+        let card = Card::new("Seven", "Red", Uuid::nil());
+        let mut log = GameLog::new();
+        log.push(GameEvent::Turn("ada".to_string()));
+        log.push(GameEvent::Placed("ada".to_string(), card));
+        log.push(GameEvent::Turn("bob".to_string()));
+        log.push(GameEvent::Drew("bob".to_string(), 2));
+        log
+    }
+
+    #[test]
+    fn derives_events_from_hand_size() {
+        let card = Card::new("Two", "Blue", Uuid::nil());
+
+        assert_eq!(GameEvent::from_status_update("ada", 0, 7, &card), None);
+        assert_eq!(GameEvent::from_status_update("ada", 5, 5, &card), None);
+        assert_eq!(
+            GameEvent::from_status_update("ada", 5, 4, &card),
+            Some(GameEvent::Placed("ada".to_string(), card.clone()))
+        );
+        assert_eq!(
+            GameEvent::from_status_update("ada", 4, 6, &card),
+            Some(GameEvent::Drew("ada".to_string(), 2))
+        );
+    }
+
+    #[test]
+    fn filters_events() {
+        let mut log = log();
+        assert_eq!(log.filtered().count(), 4);
+
+        log.filter = EventFilter::Turn;
+        let turns: Vec<usize> = log.filtered().map(|(i, _)| i).collect();
+        assert_eq!(turns, vec![0, 2]);
+
+        log.filter = EventFilter::ColorSwitch;
+        assert_eq!(log.filtered().count(), 0);
+        assert!(EventFilter::ALL
+            .iter()
+            .all(|filter| GameEvent::Turn("ada".to_string()).matches(*filter)
+                == matches!(filter, EventFilter::All | EventFilter::Turn)));
+    }
+
+    #[test]
+    fn exports_the_filtered_log() {
+        let mut log = log();
+        log.filter = EventFilter::Drew;
+
+        // Numbered by the position in the whole log
+        assert_eq!(log.to_text(Language::English), "4. bob drew 2 cards\n");
+        assert_eq!(
+            log.to_data_uri(Language::English),
+            "data:text/plain;charset=utf-8,4.%20bob%20drew%202%20cards%0A"
+        );
+    }
+
+    #[test]
+    fn encodes_uri_components() {
+        assert_eq!(encode_uri_component("a-z_0.9!~*'()"), "a-z_0.9!~*'()");
+        assert_eq!(encode_uri_component("a b&c=d/e"), "a%20b%26c%3Dd%2Fe");
+        assert_eq!(encode_uri_component("ä"), "%C3%A4");
+    }
+}
@@ -0,0 +1,425 @@
+use crate::game::Card;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+// Message bundles in a subset of the Fluent syntax, one file per language in static/locales:
+//
+// greeting = Hello { $username }
+// cards-drawn = { $n ->
+//     [one] One card was drawn
+//    *[other] { $n } cards were drawn
+// }
+//
+// Select variants are matched by exact value first and then by plural category,
+// the variant marked with * is used when nothing matches.
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Finnish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Finnish];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Finnish => "fi",
+        }
+    }
+
+    // Written in the language itself, so it can be found without understanding the current one
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Finnish => "Suomi",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL.iter().find(|l| l.code() == code).copied()
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../static/locales/en.ftl"),
+            Language::Finnish => include_str!("../static/locales/fi.ftl"),
+        }
+    }
+
+    pub fn plural_category(&self, n: usize) -> &'static str {
+        match self {
+            Language::English | Language::Finnish => {
+                if n == 1 {
+                    "one"
+                } else {
+                    "other"
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    Text(String),
+    Number(usize),
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arg::Text(text) => write!(f, "{}", text),
+            Arg::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl From<usize> for Arg {
+    fn from(n: usize) -> Self {
+        Arg::Number(n)
+    }
+}
+
+impl From<&str> for Arg {
+    fn from(text: &str) -> Self {
+        Arg::Text(text.to_string())
+    }
+}
+
+impl From<String> for Arg {
+    fn from(text: String) -> Self {
+        Arg::Text(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Element {
+    Text(String),
+    Variable(String),
+    Select(String, Vec<(String, Vec<Element>)>, usize), // variable, variants (key, pattern), default variant
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Parser {
+        Parser {
+            chars: source.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at {}", c, self.pos))
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    // A variant's pattern ends at the end of its line or at the end of the select
+    fn pattern(&mut self, variant: bool) -> Result<Vec<Element>, String> {
+        let mut elements = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => {
+                    self.pos += 1;
+                    if !text.is_empty() {
+                        elements.push(Element::Text(std::mem::take(&mut text)));
+                    }
+                    elements.push(self.placeable()?);
+                }
+                '}' | '\n' if variant => break,
+                c => {
+                    self.pos += 1;
+                    text.push(c);
+                }
+            }
+        }
+
+        let text = if variant { text.trim_end() } else { &text };
+        if !text.is_empty() {
+            elements.push(Element::Text(text.to_string()));
+        }
+
+        Ok(elements)
+    }
+
+    fn placeable(&mut self) -> Result<Element, String> {
+        self.skip_whitespace();
+        self.expect('$')?;
+        let variable = self.take_while(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Element::Variable(variable));
+        }
+
+        self.expect('-')?;
+        self.expect('>')?;
+
+        let mut variants = Vec::new();
+        let mut default = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('*') => {
+                    self.pos += 1;
+                    default = Some(variants.len());
+                }
+                None => return Err(format!("unclosed select on ${}", variable)),
+                _ => {}
+            }
+
+            self.expect('[')?;
+            let key = self.take_while(|c| c != ']' && c != '\n');
+            self.expect(']')?;
+            while self.peek() == Some(' ') {
+                self.pos += 1;
+            }
+
+            variants.push((key.trim().to_string(), self.pattern(true)?));
+        }
+
+        match default {
+            Some(default) => Ok(Element::Select(variable, variants, default)),
+            None => Err(format!("select on ${} has no default variant", variable)),
+        }
+    }
+}
+
+pub struct Bundle {
+    pub language: Language,
+    messages: HashMap<String, Vec<Element>>,
+}
+
+impl Bundle {
+    pub fn parse(language: Language, source: &str) -> Result<Bundle, String> {
+        let mut entries: Vec<(String, String)> = Vec::new();
+
+        for (i, line) in source.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // Indented lines and the closing brace of a select continue the previous message
+            if line.starts_with(char::is_whitespace) || line.starts_with('}') {
+                match entries.last_mut() {
+                    Some((_, value)) => {
+                        value.push('\n');
+                        value.push_str(line.trim());
+                    }
+                    None => return Err(format!("line {}: indented line without a message", i + 1)),
+                }
+            } else {
+                match line.split_once('=') {
+                    Some((id, value)) => {
+                        entries.push((id.trim().to_string(), value.trim().to_string()))
+                    }
+                    None => return Err(format!("line {}: expected 'id = value'", i + 1)),
+                }
+            }
+        }
+
+        let mut messages = HashMap::new();
+        for (id, value) in entries {
+            let pattern = Parser::new(&value)
+                .pattern(false)
+                .map_err(|e| format!("{}: {}", id, e))?;
+            messages.insert(id, pattern);
+        }
+
+        Ok(Bundle { language, messages })
+    }
+
+    pub fn format(&self, id: &str, args: &[(&str, Arg)]) -> Option<String> {
+        self.messages.get(id).map(|pattern| {
+            let mut output = String::new();
+            self.resolve(pattern, args, &mut output);
+            output
+        })
+    }
+
+    fn resolve(&self, pattern: &[Element], args: &[(&str, Arg)], output: &mut String) {
+        let arg = |name: &str| args.iter().find(|(n, _)| *n == name).map(|(_, arg)| arg);
+
+        pattern.iter().for_each(|element| match element {
+            Element::Text(text) => output.push_str(text),
+            Element::Variable(name) => match arg(name) {
+                Some(arg) => output.push_str(&arg.to_string()),
+                None => output.push_str(&format!("{{${}}}", name)),
+            },
+            Element::Select(name, variants, default) => {
+                let arg = arg(name);
+                let category = match arg {
+                    Some(Arg::Number(n)) => Some(self.language.plural_category(*n)),
+                    _ => None,
+                };
+
+                let variant = variants
+                    .iter()
+                    .find(|(key, _)| arg.is_some_and(|arg| key == &arg.to_string()))
+                    .or_else(|| {
+                        variants
+                            .iter()
+                            .find(|(key, _)| Some(key.as_str()) == category)
+                    })
+                    .unwrap_or(&variants[*default]);
+
+                self.resolve(&variant.1, args, output);
+            }
+        });
+    }
+}
+
+fn bundle(language: Language) -> &'static Bundle {
+    static BUNDLES: OnceLock<Vec<Bundle>> = OnceLock::new();
+
+    let bundles = BUNDLES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|l| {
+                Bundle::parse(*l, l.source()).unwrap_or_else(|_| Bundle {
+                    language: *l,
+                    messages: HashMap::new(),
+                })
+            })
+            .collect()
+    });
+
+    &bundles[Language::ALL
+        .iter()
+        .position(|l| l == &language)
+        .unwrap_or(0)]
+}
+
+fn lookup(language: Language, id: &str, args: &[(&str, Arg)]) -> Option<String> {
+    bundle(language)
+        .format(id, args)
+        .or_else(|| bundle(Language::English).format(id, args))
+}
+
+// Missing messages fall back to English and then to the message id itself
+pub fn format(language: Language, id: &str, args: &[(&str, Arg)]) -> String {
+    lookup(language, id, args).unwrap_or_else(|| id.to_string())
+}
+
+pub fn tr(language: Language, id: &str) -> String {
+    format(language, id, &[])
+}
+
+// "Red" -> "color-red", "DrawTwo" -> "type-draw-two", unknown names are shown as is
+fn name(language: Language, prefix: &str, name: &str) -> String {
+    let mut id = prefix.to_string();
+    name.chars().enumerate().for_each(|(i, c)| {
+        if i > 0 && c.is_uppercase() {
+            id.push('-');
+        }
+        id.push(c.to_ascii_lowercase());
+    });
+
+    lookup(language, &id, &[]).unwrap_or_else(|| name.to_string())
+}
+
+pub fn color_name(language: Language, color: &str) -> String {
+    name(language, "color-", color)
+}
+
+pub fn card_name(language: Language, card: &Card) -> String {
+    format(
+        language,
+        "card-name",
+        &[
+            ("color", color_name(language, &card.color).into()),
+            ("type", name(language, "type-", &card.r#type).into()),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_language_has_every_message() {
+        let english = Bundle::parse(Language::English, Language::English.source()).unwrap();
+
+        Language::ALL.iter().for_each(|language| {
+            let bundle = Bundle::parse(*language, language.source()).unwrap();
+            english.messages.keys().for_each(|id| {
+                assert!(
+                    bundle.messages.contains_key(id),
+                    "{} is missing {}",
+                    language.code(),
+                    id
+                )
+            });
+        });
+    }
+
+    #[test]
+    fn selects_plural_variants() {
+        let bundle = Bundle::parse(
+            Language::English,
+            "drawn = { $n ->\n    [0] No cards were drawn\n    [one] One card was drawn\n   *[other] { $n } cards were drawn\n}",
+        )
+        .unwrap();
+
+        let drawn = |n: usize| bundle.format("drawn", &[("n", n.into())]).unwrap();
+        assert_eq!(drawn(0), "No cards were drawn");
+        assert_eq!(drawn(1), "One card was drawn");
+        assert_eq!(drawn(3), "3 cards were drawn");
+        assert_eq!(
+            bundle.format("drawn", &[]).unwrap(),
+            "{$n} cards were drawn"
+        );
+    }
+
+    #[test]
+    fn formats_messages() {
+        let card = Card::new("DrawTwo", "Red", uuid::Uuid::nil());
+
+        assert_eq!(card_name(Language::English, &card), "Red Draw Two");
+        assert_eq!(card_name(Language::Finnish, &card), "Punainen nosta kaksi");
+        assert_eq!(tr(Language::Finnish, "no-such-message"), "no-such-message");
+        assert_eq!(
+            format(Language::Finnish, "winner", &[("username", "bob".into())]),
+            "bob voitti"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use yew::format::Json;
+use yew::services::storage::StorageService;
+
+const KEY: &str = "uno.identity";
+pub const MAX_ACCOUNT_NAME: usize = 24; // chars
+
+// Who the player is across sessions and rooms, unlike the connection id handed out in GameData.
+// Presented at Register, the identity is public and the token proves it to the server.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Identity {
+    pub identity: Uuid,
+    pub token: Uuid,
+    #[serde(default)]
+    pub account: Option<String>, // named account the identity was upgraded to
+}
+
+impl Identity {
+    pub fn generate() -> Identity {
+        Identity {
+            identity: Uuid::new_v4(),
+            token: Uuid::new_v4(),
+            account: None,
+        }
+    }
+
+    // Generated and stored on the first visit, without storage it only lasts until the page is closed
+    pub fn load(storage: &mut Option<StorageService>) -> Identity {
+        let stored = match storage {
+            Some(storage) => {
+                let Json(identity) = storage.restore(KEY);
+                identity.ok()
+            }
+            None => None,
+        };
+
+        stored.unwrap_or_else(|| {
+            let identity = Identity::generate();
+            identity.save(storage);
+            identity
+        })
+    }
+
+    pub fn save(&self, storage: &mut Option<StorageService>) {
+        if let Some(storage) = storage {
+            storage.store(KEY, Json(self));
+        }
+    }
+
+    // First block of the identity, enough to tell players apart when they share a username
+    pub fn short(identity: &Uuid) -> String {
+        identity.to_string()[..8].to_string()
+    }
+}
+
+// Account names are shown to everyone, so only plain names are accepted
+pub fn valid_account_name(name: &str) -> bool {
+    let length = name.chars().count();
+    (3..=MAX_ACCOUNT_NAME).contains(&length)
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_account_names() {
+        assert!(valid_account_name("ada_99"));
+        assert!(valid_account_name("Äiti"));
+        assert!(!valid_account_name("ab"));
+        assert!(!valid_account_name("has space"));
+        assert!(!valid_account_name(&"x".repeat(MAX_ACCOUNT_NAME + 1)));
+    }
+}
@@ -0,0 +1,47 @@
+// Keyboard controls for the game, typing into inputs is never captured
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyAction {
+    Previous,
+    Next,
+    Play,
+    Draw,
+    EndTurn,
+    Color(&'static str),
+}
+
+// Same order as the color selector
+pub const COLORS: [&str; 4] = ["Yellow", "Red", "Blue", "Green"];
+
+pub fn action(key: &str, selecting: bool) -> Option<KeyAction> {
+    if selecting {
+        return match key {
+            "1" | "2" | "3" | "4" => {
+                Some(KeyAction::Color(COLORS[key.parse::<usize>().unwrap() - 1]))
+            }
+            _ => None,
+        };
+    }
+
+    match key {
+        "ArrowLeft" => Some(KeyAction::Previous),
+        "ArrowRight" => Some(KeyAction::Next),
+        "Enter" => Some(KeyAction::Play),
+        "d" | "D" => Some(KeyAction::Draw),
+        "e" | "E" => Some(KeyAction::EndTurn),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_only_while_selecting() {
+        assert_eq!(action("3", true), Some(KeyAction::Color("Blue")));
+        assert_eq!(action("d", true), None);
+        assert_eq!(action("3", false), None);
+        assert_eq!(action("D", false), Some(KeyAction::Draw));
+        assert_eq!(action("ArrowLeft", false), Some(KeyAction::Previous));
+    }
+}
@@ -1,69 +1,221 @@
+mod animation;
+mod audio;
+mod card_face;
+mod chat;
+mod emote;
+mod friends;
 mod game;
+mod history;
+mod i18n;
+mod identity;
+mod keyboard;
+mod match_history;
+mod matchmaking;
+mod moderation;
 mod packets;
+mod palette;
+mod profile;
+mod rating;
+mod round;
+mod scoreboard;
+mod settings;
+mod statistics;
+mod table;
+mod theme;
+mod touch;
+mod validation;
 
+use animation::{Point, Scheduler};
 use anyhow::Error;
-use game::{Card, EndStatus, GameStatistics, Player};
+use audio::{Audio, Cue};
+use card_face::CardFace;
+use chat::{Channel, Chat, Entry, ServerMessage};
+use emote::Emote;
+use friends::{Friends, Invite, Presence};
+use game::{Card, Direction, EndStatus, GameOptions, GameStatistics, Player, Score, StateSnapshot};
+use history::{EventFilter, GameEvent};
+use i18n::{Arg, Language};
+use identity::Identity;
+use keyboard::KeyAction;
+use match_history::{MatchHistory, MatchRecord, Opponent};
+use matchmaking::QueueState;
+use moderation::{Block, RateLimiter};
 use packets::*;
-use std::collections::HashMap;
-use std::time::SystemTime;
-use time::OffsetDateTime;
+use palette::Palette;
+use profile::{Avatar, Profile, ProfileAvatar};
+use rating::{LeaderboardEntry, LeaderboardSource, LocalRatings};
+use round::Round;
+use scoreboard::Scoreboard;
+use settings::Settings;
+use statistics::StatisticsColumn;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+use theme::Theme;
+use touch::{CardTouch, Gesture};
+use validation::{PacketContext, PacketError};
 
 use uuid::Uuid;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
 use yew::format::Text;
 use yew::prelude::*;
+use yew::services::interval::{IntervalService, IntervalTask};
+use yew::services::keyboard::{KeyListenerHandle, KeyboardService};
+use yew::services::reader::{File, FileData, ReaderService, ReaderTask};
+use yew::services::render::RenderTask;
+use yew::services::storage::{Area, StorageService};
+use yew::services::timeout::{TimeoutService, TimeoutTask};
 use yew::services::websocket::{WebSocketService, WebSocketStatus, WebSocketTask};
-use yew::services::ConsoleService;
+use yew::services::{ConsoleService, RenderService};
+
+const SERVER: &str = "ws://127.0.0.1:8090";
+const RESYNC_INTERVAL: f64 = 5000.0; // ms
+const MOBILE_WIDTH: f64 = 700.0; // px, matches the small screen layout in index.css
+const CHAT_BOTTOM_SLACK: i32 = 30; // px from the bottom that still counts as scrolled down
 
 struct Model {
     ws: Option<WebSocketTask>,
+    queue_ws: Option<WebSocketTask>, // matchmaking, closed once a room is found
+    queue: Option<QueueState>,
+    queue_tick: Option<IntervalTask>,
+    auto_register: bool, // register as soon as the room found by matchmaking or an invite is connected
+    presence_ws: Option<WebSocketTask>, // friends' presence and invites, open outside of rooms too
+    friends: Friends,
+    friends_open: bool,
     link: ComponentLink<Self>,
 
     connected: bool,
     registered: bool,
     host: bool,
-    active: bool,
-    ended: bool,
-    turn: bool,
-    next: bool,
-    selecting: bool,
-    hovering: bool,
 
+    id: Option<Uuid>,
     username: Option<String>,
     room_id: Option<String>,
-    chat: Vec<ServerMessage>,
+    chat: Chat,
+    chat_ref: NodeRef,
+    chat_anchor: Option<i32>, // scroll height before older messages were added
     chat_input: String,
     connections: HashMap<Uuid, Player>,
-    connection_count: usize,
+    seats: Vec<Uuid>, // play order, including this client
     cards: Vec<Card>,
     allowed_cards: Vec<Card>,
-    current: Option<Card>,
 
-    end_status: Option<EndStatus>,
+    round: Round,
+    wins: HashMap<Uuid, usize>,
+    options: GameOptions,
+    score: Option<Score>,
+    round_points: Option<usize>,
+    statistics_sort: (StatisticsColumn, bool), // column, descending
+    last_resync: f64,
+    animations: Scheduler,
+    render_task: Option<RenderTask>,
+
+    storage: Option<StorageService>,
+    settings: Settings,
+    settings_open: bool,
+    identity: Identity,
+    match_history: MatchHistory,
+    history_open: bool,
+    ratings: LocalRatings,
+    rating_change: Option<(f64, f64)>, // own rating before and after the last game
+    leaderboard_open: bool,
+    leaderboard_source: LeaderboardSource,
+    server_leaderboard: Option<Vec<LeaderboardEntry>>,
+    account_input: String,
+    account_error: Option<String>,
+    audio: Audio,
+    themes: Vec<Theme>, // built-in, custom ones are kept in the settings
+    theme_error: Option<String>,
+    reader_task: Option<ReaderTask>,
+
+    focused_card: usize,
+    announcement: String,
+    touch: Option<CardTouch>,
+    preview: Option<Card>, // card held down on a touch screen
+    hold_task: Option<TimeoutTask>,
+    chat_open: bool,                // chat drawer on small screens
+    muted_players: HashSet<String>, // usernames, only for this session unlike blocks
+    rate_limiter: RateLimiter,
+    emotes: HashMap<Uuid, Emote>, // speech bubbles currently shown over players
+    emote_tasks: HashMap<Uuid, TimeoutTask>,
+    emote_limiter: RateLimiter,
+    _key_listener: KeyListenerHandle,
 }
 enum Msg {
     Connect,
     Disconnected,
     Connected,
+    QuickPlay,
+    QueueOpened,
+    CancelQueue,
+    QueueClosed,
+    QueueTick,
+    PresenceConnect,
+    PresenceOpened,
+    PresenceClosed,
+    ToggleFriends,
+    AddFriend(Uuid, String),
+    RemoveFriend(Uuid),
+    InviteFriend(Uuid),
+    AcceptInvite(Uuid),
+    DismissInvite(Uuid),
     UsernameInput(String),
+    AvatarInput(Avatar),
+    ProfileColorInput(String),
+    BioInput(String),
     RoomIDInput(String),
+    ScoreTargetInput(String),
     ChatInput(String),
+    ChatScroll,
+    LoadOlderMessages,
+    JumpToLatest,
+    React(Uuid, String), // message id, emoji
+    SelectChannel(Channel),
+    TogglePlayerMute(String),
+    ToggleBlock(String),
+    Unblock(usize), // index in the blocked players, the username might belong to someone else by now
+    WordFilterInput(String),
+    AccountInput(String),
+    ClaimAccount,
+    SendEmote(Emote),
+    EmoteExpired(Uuid),
+    ToggleEmotes,
     Register,
     StartGame,
-    sendMessage,
+    SendMessage,
     LeaveGame,
+    Rematch,
     Received(Result<String, Error>),
+    QueueReceived(Result<String, Error>),
+    PresenceReceived(Result<String, Error>),
     PlaceCard(Card),
     DrawCard,
     EndTurn,
     SwitchColor(String),
-    HoverCard(bool),
-    Error(String),
-}
-
-enum ServerMessage {
-    Join(String),
-    Leave(String),
-    Message(String, String),
+    TouchStart(usize, (f64, f64)), // card index, position
+    TouchMove((f64, f64)),
+    TouchEnd,
+    TouchCancel,
+    HoldElapsed,
+    ToggleChat,
+    LogFilter(EventFilter),
+    Resync,
+    AnimationFrame,
+    ToggleSettings,
+    ToggleMatchHistory,
+    ClearMatchHistory,
+    ToggleLeaderboard,
+    SelectLeaderboard(LeaderboardSource),
+    ToggleMute,
+    VolumeInput(String),
+    KeyDown(KeyboardEvent),
+    PaletteInput(String),
+    ToggleColorGlyphs,
+    ThemeInput(String),
+    ThemeFile(Option<File>),
+    ThemeLoaded(FileData),
+    LanguageInput(String),
+    SortStatistics(StatisticsColumn),
     Error(String),
 }
 
@@ -72,28 +224,97 @@ impl Component for Model {
     type Properties = ();
 
     fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut storage = StorageService::new(Area::Local).ok();
+        let settings = Settings::load(&storage);
+        let identity = Identity::load(&mut storage);
+        let match_history = MatchHistory::load(&storage);
+        let ratings = LocalRatings::load(&storage);
+        let friends = Friends::load(&storage);
+        link.send_message(Msg::PresenceConnect);
+        set_document_language(settings.language);
+
+        let key_listener = KeyboardService::register_key_down(
+            &yew::utils::document(),
+            link.batch_callback(|e: KeyboardEvent| {
+                // Form controls handle their own keys, and shortcuts of the browser are left alone
+                let control = e.target().is_some_and(|t| {
+                    t.dyn_ref::<web_sys::HtmlInputElement>().is_some()
+                        || t.dyn_ref::<web_sys::HtmlSelectElement>().is_some()
+                        || t.dyn_ref::<web_sys::HtmlTextAreaElement>().is_some()
+                        || t.dyn_ref::<web_sys::HtmlButtonElement>().is_some()
+                });
+                let modified = e.ctrl_key() || e.meta_key() || e.alt_key();
+
+                if control || modified {
+                    None
+                } else {
+                    Some(Msg::KeyDown(e))
+                }
+            }),
+        );
+
         Self {
             ws: None,
+            queue_ws: None,
+            queue: None,
+            queue_tick: None,
+            auto_register: false,
+            presence_ws: None,
+            friends,
+            friends_open: false,
             link,
             connected: false,
             registered: false,
             host: false,
-            active: false,
-            ended: false,
-            turn: false,
-            next: false,
-            selecting: false,
-            hovering: false,
+            id: None,
             username: None,
             room_id: Some("c05554ae-b4ee-4976-ac05-97aaf3c98a24".to_string()),
-            chat: Vec::new(),
+            chat: Chat::new(),
+            chat_ref: NodeRef::default(),
+            chat_anchor: None,
             chat_input: String::new(),
             connections: HashMap::new(),
-            connection_count: 1,
+            seats: Vec::new(),
             cards: Vec::new(),
             allowed_cards: Vec::new(),
-            current: None,
-            end_status: None,
+            round: Round::new(),
+            wins: HashMap::new(),
+            options: GameOptions::new(),
+            score: None,
+            round_points: None,
+            statistics_sort: (StatisticsColumn::CardsPlaced, true),
+            last_resync: 0.0,
+            animations: Scheduler::new(),
+            render_task: None,
+            storage,
+            settings,
+            settings_open: false,
+            identity,
+            match_history,
+            history_open: false,
+            ratings,
+            rating_change: None,
+            leaderboard_open: false,
+            leaderboard_source: LeaderboardSource::Server,
+            server_leaderboard: None,
+            account_input: String::new(),
+            account_error: None,
+            audio: Audio::new(),
+            themes: Theme::built_in(),
+            theme_error: None,
+            reader_task: None,
+            focused_card: 0,
+            announcement: String::new(),
+            touch: None,
+            preview: None,
+            hold_task: None,
+            chat_open: false,
+            muted_players: HashSet::new(),
+            rate_limiter: RateLimiter::new(moderation::CHAT_LIMIT),
+            emotes: HashMap::new(),
+            emote_tasks: HashMap::new(),
+            emote_limiter: RateLimiter::new(emote::EMOTE_LIMIT),
+            _key_listener: key_listener,
         }
     }
 
@@ -103,17 +324,17 @@ impl Component for Model {
                 ConsoleService::log("Connecting");
 
                 let cbout = self.link.callback(Msg::Received);
-                let cbnot = self.link.callback(|input| match input {
+                let failed = self.t("connect-failed");
+                let cbnot = self.link.callback(move |input| match input {
                     WebSocketStatus::Closed => Msg::Disconnected,
-                    WebSocketStatus::Error => {
-                        Msg::Error("Failed to connect to servers".to_string())
-                    }
+                    WebSocketStatus::Error => Msg::Error(failed.clone()),
                     _ => Msg::Connected,
                 });
                 if self.ws.is_none() {
                     let task = WebSocketService::connect_text(
                         &format!(
-                            "ws://127.0.0.1:8090/{}",
+                            "{}/{}",
+                            SERVER,
                             self.room_id
                                 .clone()
                                 .expect("Cannot join game with the room_id 'None'")
@@ -132,173 +353,693 @@ impl Component for Model {
             }
             Msg::Connected => {
                 self.connected = true;
+
+                if self.auto_register {
+                    self.auto_register = false;
+                    self.update(Msg::Register);
+                }
+                true
+            }
+            Msg::QuickPlay => {
+                if self.ws.is_some() || self.queue_ws.is_some() {
+                    return false;
+                }
+
+                let failed = self.t("queue-failed");
+                let task = WebSocketService::connect_text(
+                    &format!("{}/{}", SERVER, matchmaking::QUEUE_PATH),
+                    self.link.callback(Msg::QueueReceived),
+                    self.link.callback(move |status| match status {
+                        WebSocketStatus::Opened => Msg::QueueOpened,
+                        WebSocketStatus::Closed => Msg::QueueClosed,
+                        WebSocketStatus::Error => Msg::Error(failed.clone()),
+                    }),
+                );
+
+                match task {
+                    Ok(task) => {
+                        self.queue_ws = Some(task);
+                        self.queue = Some(QueueState::new(now()));
+                        self.queue_tick = Some(IntervalService::spawn(
+                            Duration::from_millis(matchmaking::TICK),
+                            self.link.callback(|_| Msg::QueueTick),
+                        ));
+                    }
+                    Err(e) => self.post(ServerMessage::Error(e.to_string())),
+                }
+                true
+            }
+            // Nothing can be sent before the connection is open
+            Msg::QueueOpened => {
+                let username = self
+                    .username
+                    .clone()
+                    .unwrap_or_else(|| self.t("default-username"));
+                // Only kept on this device, so anyone can send any rating. The server may use it as a
+                // starting point for players it hasn't rated itself, but must never trust it.
+                let rating = self.ratings.rating(&username, Some(self.identity.identity));
+
+                if let Some(ref mut task) = self.queue_ws {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::QueueJoin(
+                        username,
+                        self.identity.clone(),
+                        rating,
+                    )))));
+                }
+                false
+            }
+            Msg::CancelQueue => {
+                if let Some(ref mut task) = self.queue_ws {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::QueueLeave))));
+                }
+                self.leave_queue();
+                true
+            }
+            Msg::QueueClosed => {
+                // Closed by the server without a match, the player can try again
+                if self.queue.is_some() {
+                    self.leave_queue();
+                    self.post(ServerMessage::Warning(self.t("queue-closed")));
+                }
                 true
             }
+            Msg::QueueTick => self.queue.is_some(),
+            Msg::PresenceConnect => {
+                if self.presence_ws.is_some() {
+                    return false;
+                }
+
+                // Presence is optional, a server without it just leaves everyone offline
+                let task = WebSocketService::connect_text(
+                    &format!("{}/{}", SERVER, friends::PRESENCE_PATH),
+                    self.link.callback(Msg::PresenceReceived),
+                    self.link.callback(|status| match status {
+                        WebSocketStatus::Opened => Msg::PresenceOpened,
+                        _ => Msg::PresenceClosed,
+                    }),
+                );
+
+                match task {
+                    Ok(task) => self.presence_ws = Some(task),
+                    Err(e) => ConsoleService::log(&format!("No presence connection: {}", e)),
+                }
+                false
+            }
+            Msg::PresenceOpened => {
+                self.send_presence();
+                true
+            }
+            Msg::PresenceClosed => {
+                self.presence_ws = None;
+                self.friends.presence.clear();
+                true
+            }
+            Msg::ToggleFriends => {
+                self.friends_open = !self.friends_open;
+                if self.friends_open {
+                    self.update(Msg::PresenceConnect);
+                }
+                true
+            }
+            Msg::AddFriend(identity, username) => {
+                self.friends.add(identity, username);
+                self.friends.save(&mut self.storage);
+                self.follow_friends();
+                true
+            }
+            Msg::RemoveFriend(identity) => {
+                self.friends.remove(&identity);
+                self.friends.save(&mut self.storage);
+                self.follow_friends();
+                true
+            }
+            Msg::InviteFriend(identity) => match (&mut self.presence_ws, &self.room_id) {
+                (Some(task), Some(room)) if self.registered => {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::Invite(
+                        identity,
+                        room.clone(),
+                    )))));
+                    true
+                }
+                _ => false,
+            },
+            Msg::AcceptInvite(identity) => {
+                let invite = match self.friends.take_invite(&identity) {
+                    Some(invite) => invite,
+                    None => return false,
+                };
+
+                // Leaving resets the username, the invited player keeps theirs
+                let username = self.username.clone();
+                self.update(Msg::LeaveGame);
+                self.username = username;
+                self.room_id = Some(invite.room);
+                self.auto_register = self.username.as_deref().is_some_and(|u| !u.is_empty());
+                self.friends_open = false;
+                self.update(Msg::Connect);
+                true
+            }
+            Msg::DismissInvite(identity) => self.friends.take_invite(&identity).is_some(),
             Msg::UsernameInput(e) => {
                 self.username = Some(e);
                 true
             }
+            Msg::AvatarInput(avatar) => {
+                self.settings.profile.avatar = avatar;
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::ProfileColorInput(e) => {
+                self.settings.profile.color = Some(e);
+                self.settings.profile = self.settings.profile.clone().sanitized();
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::BioInput(e) => {
+                self.settings.profile.bio = e;
+                self.settings.profile = self.settings.profile.clone().sanitized();
+                self.settings.save(&mut self.storage);
+                true
+            }
             Msg::RoomIDInput(e) => {
                 self.room_id = Some(e);
                 true
             }
+            Msg::ScoreTargetInput(e) => {
+                // Anything but a positive number plays a single round
+                self.options.score_target = e.trim().parse().ok().filter(|t| *t > 0);
+                true
+            }
             Msg::ChatInput(e) => {
                 self.chat_input = e;
                 true
             }
-            Msg::Register => match self.ws {
-                Some(ref mut task) => {
-                    task.send::<Text>(Text::into(Ok(to_json(PacketType::Register(
-                        self.username.clone().unwrap_or("player".to_string()),
-                    )))));
-                    true
+            Msg::Register => {
+                let username = self
+                    .username
+                    .clone()
+                    .unwrap_or_else(|| self.t("default-username"));
+
+                match self.ws {
+                    Some(ref mut task) => {
+                        task.send::<Text>(Text::into(Ok(to_json(PacketType::Register(
+                            username,
+                            self.settings.profile.clone(),
+                            self.identity.clone(),
+                        )))));
+                        // The name friends see is only settled once it's registered with
+                        self.send_presence();
+                        true
+                    }
+                    None => false,
                 }
-                None => false,
-            },
+            }
             Msg::StartGame => match self.ws {
                 Some(ref mut task) => {
                     task.send::<Text>(Text::into(Ok(to_json(PacketType::StartGame(
-                        "None".to_string(),
+                        serde_json::to_string(&self.options).unwrap(),
                     )))));
+                    self.apply_options(self.options.clone());
                     true
                 }
                 None => false,
             },
-            Msg::sendMessage => match self.ws {
+            Msg::SendMessage => match self.ws {
+                Some(_) if self.chat_input.trim().is_empty() => false,
+                Some(_) if !self.can_write(self.chat.channel) => false,
+                Some(_) if self.rate_limiter.try_send(now()).is_err() => {
+                    self.warn_rate_limited();
+                    true
+                }
                 Some(ref mut task) => {
-                    task.send::<Text>(Text::into(Ok(to_json(PacketType::Message(
-                        self.username.clone().unwrap_or("Unknown".to_string()),
+                    // The server fills in the sender
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::ChannelMessage(
+                        self.chat.channel,
                         self.chat_input.clone(),
                     )))));
                     self.chat_input = String::new();
+                    self.chat.pin();
                     true
                 }
                 None => false,
             },
+            Msg::ChatScroll => {
+                let element = match self.chat_ref.cast::<Element>() {
+                    Some(element) => element,
+                    None => return false,
+                };
+
+                let bottom =
+                    element.scroll_height() - element.scroll_top() - element.client_height();
+                if bottom < CHAT_BOTTOM_SLACK {
+                    let render = !self.chat.pinned || self.chat.unread(self.chat.channel) > 0;
+                    self.chat.pin();
+                    render
+                } else {
+                    self.chat.pinned = false;
+                    if element.scroll_top() <= 0 && self.chat.has_older() {
+                        self.update(Msg::LoadOlderMessages)
+                    } else {
+                        false
+                    }
+                }
+            }
+            Msg::LoadOlderMessages => {
+                self.chat_anchor = self
+                    .chat_ref
+                    .cast::<Element>()
+                    .map(|e| e.scroll_height() - e.scroll_top());
+                self.chat.pinned = false;
+                self.chat.load_older();
+                true
+            }
+            Msg::JumpToLatest => {
+                self.chat.pin();
+                true
+            }
+            Msg::SelectChannel(channel) => {
+                self.chat.select(channel);
+                self.chat_open = true;
+                true
+            }
+            Msg::React(message, emoji) => {
+                let id = self.self_id();
+                let added = match self.chat.reactable(message) {
+                    Some(entry) => !entry
+                        .reactions
+                        .iter()
+                        .any(|(r, ids)| r == &emoji && ids.contains(&id)),
+                    None => return false,
+                };
+
+                // The server fills in the reactor, the reaction is shown once it comes back
+                if let Some(ref mut task) = self.ws {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::Reaction(
+                        message, emoji, added,
+                    )))));
+                }
+                false
+            }
             Msg::LeaveGame => {
                 self.ws = None;
+                self.leave_queue();
+                self.auto_register = false;
                 self.connected = false;
                 self.registered = false;
                 self.host = false;
-                self.active = false;
-                self.ended = false;
-                self.turn = false;
-                self.next = false;
 
+                self.id = None;
                 self.username = None;
                 self.room_id = Some("c05554ae-b4ee-4976-ac05-97aaf3c98a24".to_string());
-                self.chat = Vec::new();
+                self.chat = Chat::new();
                 self.connections = HashMap::new();
-                self.connection_count = 1;
-                self.cards = Vec::new();
+                self.seats = Vec::new();
+                self.set_cards(Vec::new());
                 self.allowed_cards = Vec::new();
-                self.current = None;
-                self.end_status = None;
+                self.round = Round::new();
+                self.wins = HashMap::new();
+                self.options = GameOptions::new();
+                self.score = None;
+                self.round_points = None;
 
                 true
             }
+            // The board is only reset once the server starts the next round
+            Msg::Rematch => match self.ws {
+                Some(ref mut task) => {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::RematchVote(true)))));
+                    false
+                }
+                None => false,
+            },
             Msg::Received(Ok(s)) => {
-                let json: Result<PacketType, serde_json::Error> = serde_json::from_str(&s);
+                let packet = match packets::receive(Socket::Room, &s) {
+                    Ok(Some(packet)) => {
+                        validation::validate(&packet, &self.packet_context()).map(|_| packet)
+                    }
+                    Ok(None) => {
+                        ConsoleService::error(&format!(
+                            "Dropped a packet not meant for the room: {}",
+                            s
+                        ));
+                        return false;
+                    }
+                    Err(e) => Err(PacketError::Malformed(e.to_string())),
+                };
 
-                if let Ok(packet) = json {
-                    match packet {
-                        PacketType::Register(_) => {}
+                match packet {
+                    Err(e) => self.reject_packet(e, &s),
+                    Ok(packet) => match packet {
+                        PacketType::Register(..) => {}
                         PacketType::GameData(self_id, _self_username, connections) => {
                             self.registered = true;
+                            self.id = Some(self_id);
 
-                            connections.iter().for_each(|(id, username)| {
-                                if id != &self_id {
+                            // Late joins need the state of a game that might already be running
+                            self.link.send_message(Msg::Resync);
+
+                            // The server lists connections in join order, used until it sends the seat order
+                            connections.into_iter().for_each(|(id, username, profile)| {
+                                self.seat(id);
+
+                                if id != self_id {
                                     // Insert connection to the connection list
                                     if let std::collections::hash_map::Entry::Vacant(e) =
-                                        self.connections.entry(*id)
+                                        self.connections.entry(id)
                                     {
-                                        e.insert(Player::new(
-                                            username.clone(),
-                                            0,
-                                            self.connection_count,
-                                        ));
+                                        let mut player = Player::new(username, 0, 0);
+                                        player.profile = profile.sanitized();
+                                        e.insert(player);
                                     }
                                 }
                             });
+                            self.reindex();
                         }
-                        PacketType::Connect(id, username) => {
-                            self.connection_count += 1;
-
-                            self.chat.push(ServerMessage::Join(username.clone()));
+                        PacketType::Connect(id, username, profile) => {
+                            self.post(ServerMessage::Join(username.clone()));
 
                             // Insert connection to the connection list
                             if let std::collections::hash_map::Entry::Vacant(e) =
                                 self.connections.entry(id)
                             {
-                                e.insert(Player::new(username, 0, self.connection_count));
+                                let mut player = Player::new(username, 0, 0);
+                                player.profile = profile.sanitized();
+                                e.insert(player);
                             }
+                            self.seat(id);
+                            self.reindex();
                         }
                         PacketType::Disconnect(id, username) => {
-                            self.connection_count -= 1;
-
-                            self.chat.push(ServerMessage::Leave(username));
+                            self.post(ServerMessage::Leave(username));
 
                             self.connections.remove(&id);
+                            self.seats.retain(|s| s != &id);
+                            self.round.rematch_votes.leave(&id);
+                            self.reindex();
+                        }
+                        PacketType::SeatOrder(seats) => {
+                            self.seats = seats;
+                            self.reindex();
+                        }
+                        PacketType::DirectionUpdate(direction) => {
+                            self.round.direction = direction;
                         }
                         PacketType::Message(username, content) => {
                             if content == "You are the host" {
                                 self.host = true;
                             }
 
-                            self.chat.push(ServerMessage::Message(username, content));
+                            self.post(ServerMessage::Message(username, content));
+                        }
+                        PacketType::ChannelMessageUpdate(message, sender, channel, content) => {
+                            let username = match self.connections.get(&sender) {
+                                Some(player) => player.username.clone(),
+                                None => self.display_username(),
+                            };
+
+                            match self.route(sender, channel) {
+                                Some(channel) => self.post_to(
+                                    Some(message),
+                                    ServerMessage::Message(username, content),
+                                    channel,
+                                ),
+                                None => ConsoleService::error(&format!(
+                                    "Dropped a message from {} not meant for this client",
+                                    username
+                                )),
+                            }
+                        }
+                        PacketType::ChannelMessage(..) => {} // will never be received by client
+                        PacketType::IdentityUpdate(id, identity, account) => {
+                            self.apply_identity(id, identity, account);
+                        }
+                        PacketType::ClaimAccount(_) => {} // will never be received by client
+                        PacketType::LeaderboardUpdate(entries) => {
+                            self.server_leaderboard = Some(entries);
+                        }
+                        PacketType::FetchLeaderboard(_) => {} // will never be received by client
+                        PacketType::QueueJoin(..)
+                        | PacketType::QueueLeave
+                        | PacketType::QueueStatus(..)
+                        | PacketType::MatchFound(_) => {} // only accepted from the queue
+                        PacketType::PresenceHello(..)
+                        | PacketType::PresenceFollow(_)
+                        | PacketType::PresenceUpdate(..)
+                        | PacketType::Invite(..)
+                        | PacketType::InviteReceived(..) => {} // only accepted from presence
+                        PacketType::EmoteUpdate(id, emote) => {
+                            let silenced = self
+                                .connections
+                                .get(&id)
+                                .is_some_and(|player| self.silenced(&player.username));
+
+                            if self.settings.emotes && !silenced {
+                                self.show_emote(id, emote);
+                            }
                         }
+                        PacketType::Emote(_) => {} // will never be received by client
+                        PacketType::ReactionUpdate(id, message, emoji, added) => {
+                            self.chat.react(id, message, &emoji, added);
+                        }
+                        PacketType::Reaction(..) => {} // will never be received by client
                         PacketType::StartGame(_) => {} // will never be received by client
                         PacketType::StatusUpdatePublic(id, _username, card_count, current) => {
-                            self.connections.get_mut(&id).unwrap().card_count = card_count;
-                            self.active = true;
-                            self.current = Some(current);
+                            self.start_round();
+                            let player = match self.connections.get_mut(&id) {
+                                Some(player) => player,
+                                None => {
+                                    self.reject_packet(PacketError::UnknownPlayer(id), &s);
+                                    return true;
+                                }
+                            };
+                            let previous = player.card_count;
+                            let event = GameEvent::from_status_update(
+                                &player.username,
+                                player.card_count,
+                                card_count,
+                                &current,
+                            );
+                            player.card_count = card_count;
+                            if let Some(event) = event {
+                                self.record(id, event);
+                            }
+                            if card_count == 1 && previous > 1 {
+                                self.play(Cue::Uno);
+                            }
+
+                            let seat = self.seat_point(&id);
+                            if card_count < previous {
+                                self.animate(vec![Some(current.clone())], seat, Point::PILE);
+                            } else if card_count > previous {
+                                self.animate(vec![None; card_count - previous], Point::DECK, seat);
+                            }
+                            self.round.current = Some(current);
                         }
                         PacketType::StatusUpdatePrivate(cards, current) => {
-                            self.cards = cards;
-                            self.current = Some(current);
+                            self.start_round();
+                            if let Some(event) = GameEvent::from_status_update(
+                                &self.display_username(),
+                                self.cards.len(),
+                                cards.len(),
+                                &current,
+                            ) {
+                                self.record(self.self_id(), event);
+                            }
+                            if cards.len() == 1 && self.cards.len() > 1 {
+                                self.play(Cue::Uno);
+                            }
+
+                            let (added, removed) = animation::diff_hand(&self.cards, &cards);
+                            self.animate(
+                                removed.into_iter().map(Some).collect(),
+                                Point::HAND,
+                                Point::PILE,
+                            );
+                            self.animate(
+                                added.into_iter().map(Some).collect(),
+                                Point::DECK,
+                                Point::HAND,
+                            );
+                            self.set_cards(cards);
+                            self.round.current = Some(current);
                         }
                         PacketType::AllowedCardsUpdate(cards) => {
                             self.allowed_cards = cards;
-                            self.turn = true;
+                            self.round.turn = true;
+                            self.play(Cue::Turn);
                         }
                         PacketType::DrawCard(_) => {} // will never be received by client
                         PacketType::PlaceCard(_) => {} // will never be received by client
                         PacketType::EndTurn => {
                             ConsoleService::log("[MESSAGE] Your turn has ended.");
                             self.allowed_cards.clear();
-                            self.turn = false;
+                            self.round.turn = false;
+                        }
+                        PacketType::ColorSwitch(color) => {
+                            self.round.color = Some(color.clone());
+
+                            // Our own switches are logged when they are sent
+                            if !self.round.turn {
+                                let username = self
+                                    .connections
+                                    .values()
+                                    .find(|p| p.turn)
+                                    .map(|p| p.username.clone())
+                                    .unwrap_or_else(|| self.t("unknown-player"));
+
+                                self.round.log.push(GameEvent::ColorSwitch(username, color));
+                            }
                         }
-                        PacketType::ColorSwitch(_) => todo!(),
                         PacketType::TurnUpdate(id, next) => {
+                            let username = match self.connections.get(&id) {
+                                Some(player) => player.username.clone(),
+                                None => self.display_username(),
+                            };
+                            self.round.tracker.turn(id, &username, now());
+                            self.announcement = if self.connections.contains_key(&id) {
+                                self.t_args(
+                                    "announce-turn",
+                                    &[("username", username.as_str().into())],
+                                )
+                            } else {
+                                self.t("announce-own-turn")
+                            };
+                            self.round.log.push(GameEvent::Turn(username));
+
                             self.connections.iter_mut().for_each(|p| {
                                 p.1.turn = &id == p.0;
                                 p.1.next = &next == p.0;
                             });
 
-                            if !self.connections.contains_key(&next) {
-                                self.next = true;
-                            } else {
-                                self.next = false;
-                            }
+                            self.round.next = !self.connections.contains_key(&next);
                         }
                         PacketType::Error(_code, body) => {
-                            self.chat.push(ServerMessage::Error(body));
+                            self.post(ServerMessage::Error(body));
+                        }
+                        PacketType::OptionsUpdate(options) => {
+                            self.apply_options(options);
                         }
-                        PacketType::WinUpdate(id, username, placements, statistics) => {
-                            self.end_status = Some(EndStatus {
+                        PacketType::RematchVoteUpdate(id, ready) => {
+                            self.round.rematch_votes.vote(id, ready);
+                        }
+                        PacketType::RematchVote(_) => {} // will never be received by client
+                        PacketType::WinUpdate(id, username, placements, mut statistics) => {
+                            let players = self.round.tracker.finish(now());
+                            if statistics.players.is_empty() {
+                                statistics.players = players;
+                            }
+
+                            *self.wins.entry(id).or_insert(0) += 1;
+
+                            if let Some(score) = &mut self.score {
+                                // Every client has to award the same points, so only what the server
+                                // revealed counts. Our own hand alone would differ from the others'.
+                                self.round_points = match statistics.points {
+                                    Some(points) => Some(score.credit(id, points)),
+                                    None if !statistics.hands.is_empty() => {
+                                        Some(score.award(id, &statistics.hands))
+                                    }
+                                    None => {
+                                        score.skip();
+                                        None
+                                    }
+                                };
+                            }
+
+                            self.record_match(id, &username, &placements, &statistics);
+
+                            self.round.end_status = Some(EndStatus {
                                 winner_id: id,
                                 winner: username,
                                 placements,
                                 statistics,
                             });
-                            self.ended = true;
+                            self.round.ended = true;
+                            self.play(Cue::Win);
+                        }
+                        PacketType::Resync => {} // will never be received by client
+                        PacketType::StateSnapshot(snapshot) => {
+                            self.apply_snapshot(snapshot);
+                        }
+                    },
+                }
+
+                true
+            }
+            // Only matchmaking packets, nothing here can touch the room
+            Msg::QueueReceived(Ok(s)) => {
+                let packet = match packets::receive(Socket::Queue, &s) {
+                    Ok(Some(packet))
+                        if validation::validate(&packet, &self.packet_context()).is_ok() =>
+                    {
+                        packet
+                    }
+                    _ => {
+                        ConsoleService::error(&format!("Dropped a packet from the queue: {}", s));
+                        return false;
+                    }
+                };
+
+                match packet {
+                    PacketType::QueueStatus(position, estimate) => {
+                        if let Some(queue) = &mut self.queue {
+                            queue.update(position, estimate, now());
                         }
                     }
+                    PacketType::MatchFound(room) => {
+                        // Into the waiting room like a room joined by id
+                        self.leave_queue();
+                        self.room_id = Some(room);
+                        self.auto_register = true;
+                        self.update(Msg::Connect);
+                        self.play(Cue::MatchFound);
+                    }
+                    _ => {} // will never be received by client
                 }
+                true
+            }
+            // Only presence of friends and invites, nothing here can touch the room
+            Msg::PresenceReceived(Ok(s)) => {
+                let packet = match packets::receive(Socket::Presence, &s) {
+                    Ok(Some(packet))
+                        if validation::validate(&packet, &self.packet_context()).is_ok() =>
+                    {
+                        packet
+                    }
+                    _ => {
+                        ConsoleService::error(&format!("Dropped a packet from presence: {}", s));
+                        return false;
+                    }
+                };
+
+                match packet {
+                    PacketType::PresenceUpdate(identity, presence)
+                        if self.friends.contains(&identity) =>
+                    {
+                        self.friends.presence.insert(identity, presence);
+                    }
+                    PacketType::InviteReceived(identity, username, room) => {
+                        let blocked = self
+                            .settings
+                            .blocked
+                            .iter()
+                            .any(|b| b.matches(&username, Some(identity)));
 
+                        if !blocked
+                            && self.friends.invite(Invite {
+                                identity,
+                                username,
+                                room,
+                            })
+                        {
+                            self.play(Cue::Mention);
+                        }
+                    }
+                    _ => {} // sent by the client, or not about a friend
+                }
                 true
             }
-            Msg::Received(Err(s)) => {
+            Msg::Received(Err(s)) | Msg::QueueReceived(Err(s)) | Msg::PresenceReceived(Err(s)) => {
                 ConsoleService::error(&format!("Received invalid data from the server! {}", s));
                 false
             }
@@ -310,7 +1051,7 @@ impl Component for Model {
                 }
 
                 if card.r#type == "Switch" || card.r#type == "DrawFour" {
-                    self.selecting = true;
+                    self.round.selecting = true;
                 }
 
                 true
@@ -334,152 +1075,695 @@ impl Component for Model {
             }
             Msg::SwitchColor(color) => {
                 if let Some(ref mut task) = self.ws {
-                    task.send::<Text>(Text::into(Ok(to_json(PacketType::ColorSwitch(color)))));
-                    self.selecting = false;
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::ColorSwitch(
+                        color.clone(),
+                    )))));
+                    self.round.selecting = false;
+                    self.round.color = Some(color.clone());
+                    self.round
+                        .log
+                        .push(GameEvent::ColorSwitch(self.display_username(), color));
                 }
 
                 true
             }
-            Msg::HoverCard(active) => {
-                self.hovering = active;
-                true
-            }
-            Msg::Error(e) => {
-                self.chat.push(ServerMessage::Error(e.clone()));
-                ConsoleService::log(&e);
+            Msg::TouchStart(index, position) => {
+                self.touch = Some(CardTouch::new(index, position, now()));
+                self.hold_task = Some(TimeoutService::spawn(
+                    Duration::from_millis(touch::HOLD_DURATION),
+                    self.link.callback(|_| Msg::HoldElapsed),
+                ));
                 false
             }
-        }
-    }
-
-    fn change(&mut self, _prop: Self::Properties) -> ShouldRender {
-        false
-    }
-
-    fn view(&self) -> Html {
-        html! {
-            // <div></div>
-            <div class="container">
-                // login screen element
-                <div class="connect-screen" style={format!("display: {}", if !self.registered {"flex"} else {"none"})}>
-                    <h1>{"Enter Room ID"}</h1>
-                    <input type="text" placeholder="Please enter a valid room-id" value=self.room_id.clone() oninput=self.link.callback(|e: InputData| Msg::RoomIDInput(e.value))/><br/>
-                    <button disabled={self.room_id.is_none() ||self.room_id == Some("".to_string())} onclick=self.link.callback(|_| Msg::Connect)>{ "Connect" }</button>
-
-                    <h1 hidden={self.ws.is_none()}>{"Enter your username"}</h1>
-                    <input hidden={self.ws.is_none()} type="text" placeholder="Please enter a valid username" value=self.username.clone() oninput=self.link.callback(|e: InputData| Msg::UsernameInput(e.value))/>
-                    <button hidden={self.ws.is_none()} disabled={self.username.is_none() || self.username == Some("".to_string())} onclick=self.link.callback(|_| Msg::Register)>{ "Register" }</button>
-
-                    <ul class="connection-status"><a>{ "Connected: "}</a><a style={if self.ws.is_none() {"color: var(--red)"} else {"color: var(--green)"}}>{ self.ws.is_some() }</a></ul>
-
-                </div>
-
-                <div class="waiting-screen" style={format!("display: {}", if self.registered && !self.active {"flex"} else {"none"})} >
-                    <h1>{"Waiting for game to start"}</h1>
-                    <h2>{"This room's ID"}</h2>
-                    <h3>{self.room_id.clone().unwrap_or("Invalid ID, please refresh your page.".to_string())}</h3>
-
-                    <p hidden={!self.host}>{"You are the host"}</p>
-                    <button hidden={!self.host} disabled={self.connection_count <= 1} onclick=self.link.callback(|_| Msg::StartGame)>{ "Start game" }</button>
-                </div>
-
-                <div class="cards-container" style={format!("display: {}", if self.active {"flex"} else {"none"})} >
-                    // <button onmouseover=self.link.callback(|_| Msg::HoverCard(true))  onmouseout=self.link.callback(|_| Msg::HoverCard(false)) class="card" id="allowed" style="background-image: url(http://localhost/uno-api/cards/Blue.Block.png);"></button>
-                    // <button class="card" id="disallowed" style="background-image: url(static/img/Blue.DrawFour.svg);" disabled={true}></button>
-                    { for self.cards.iter().to_owned().map(|card| {
-                        let c = card.clone();
-                        html! {
-                            <button
-                                class="card"
-                                onclick=self.link.callback(move |_|  Msg::PlaceCard(c.clone()) )
-                                onmouseover=self.link.callback(|_| Msg::HoverCard(true))  onmouseout=self.link.callback(|_| Msg::HoverCard(false))
-                                style=format!("background-image: url(static/img/{}.{}.svg);", card.color, card.r#type)
-                                id={(if self.allowed_cards.contains(card) {"allowed"} else {"disallowed"}).to_string()}
-                                disabled={!self.allowed_cards.contains(card)} >
-                            </button>
-                            }
-                        })
+            Msg::TouchMove(position) => match &mut self.touch {
+                Some(touch) => {
+                    touch.current = position;
+                    if !touch.is_still() {
+                        self.hold_task = None;
                     }
-                    </div>
-                    <h2 hidden={!self.active} id="status-text"> { if self.turn && !self.selecting{"Your turn.".to_string()} else {"Waiting for the opponent".to_string()} }</h2>
-                    <h1 style={ if self.hovering {"opacity: 100%;"} else {"opacity: 0;"}} id="place-card-text">{"Place a card."}</h1>
-                // End turn button
-                <button onclick=self.link.callback(|_| Msg::EndTurn) class="end-turn-button" style={format!("display: {}", if self.active {"flex"} else {"none"})} ><h1>{"End your turn"}</h1></button>
-
-                <div class="deck-container" style={format!("display: {}", if self.active {"flex"} else {"none"})}  >
-                    <button class="card" id="deck" onclick=self.link.callback(|_| Msg::DrawCard)><div class="logo"></div></button>
-                    <button class="card" id="deck"><div class="logo"></div></button>
-                    <button class="card" id="deck"><div class="logo"></div></button>
-                    <button class="card" id="deck"><div class="logo"></div></button>
-                    <div
-                        class="card" id="placed-deck" style={
-                            if self.current.is_some(){
-                                format!("background-image: url(static/img/{}.{}.svg);",
-                                self.current.clone().unwrap().color, self.current.clone().unwrap().r#type)
-                            } else {
-                                "static/img/uno.svg);".to_string()
-                            }
-                        } >
-                    </div>
-                    <h1 class="draw-card-text">{"Draw a card."}</h1>
-                </div>
-
-                <div class="player-list">
-                    <div class="player-object" id="player-self" style={"order: -1;"}>
-                        <div class="player-detail"></div>
-                        <h2>{self.cards.len()}</h2>
-                        <h1 style={if self.turn {"color: var(--green)"} else {"color: white"}}>
-                        {format!("{} [You]", self.username.clone().unwrap_or_else(|| "unset".to_string()))}
-                        </h1>
-                        {if self.next {html! {<h3>{"[Next]"}</h3>}} else if self.turn {html!{<h4>{"[Turn]"}</h4>}} else {html!{<h3></h3>}}}
-                    </div>
+                    true
+                }
+                None => false,
+            },
+            Msg::HoldElapsed => {
+                self.hold_task = None;
 
-                    {
-                        for self.connections.iter().map(|(_id, player)| {
+                match &mut self.touch {
+                    Some(touch) if touch.is_still() => {
+                        touch.held = true;
+                        self.preview = self.cards.get(touch.index).cloned();
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            // The click following a touch is suppressed, so taps play cards from here
+            Msg::TouchEnd => {
+                self.hold_task = None;
+                self.preview = None;
 
-                            html! {
-                                <div class="player-object" id="player-self" style={format!("order: {};", player.index)}>
-                                    <div class="player-detail"></div>
-                                    <h2>{player.card_count}</h2>
-                                    <h1
-                                    style={if player.turn {"color: var(--green)"} else {"color: white"}}
-                                    >
-                                    {&player.username}
-                                    </h1>
-                                    {if player.next {html! {<h3>{"[Next]"}</h3>}} else if player.turn {html!{<h4>{"[Turn]"}</h4>}} else {html!{<h3></h3>}}}
-                                </div>
+                match self.touch.take() {
+                    Some(touch) => match touch.gesture(now()) {
+                        Gesture::Tap | Gesture::SwipeUp => match self.cards.get(touch.index) {
+                            Some(card) if self.allowed_cards.contains(card) => {
+                                self.update(Msg::PlaceCard(card.clone()))
                             }
-                        })
+                            _ => true,
+                        },
+                        Gesture::Hold | Gesture::Scroll => true,
+                    },
+                    None => false,
+                }
+            }
+            Msg::TouchCancel => {
+                self.touch = None;
+                self.hold_task = None;
+                self.preview = None;
+                true
+            }
+            Msg::ToggleChat => {
+                self.chat_open = !self.chat_open;
+                if self.chat_open && self.chat.pinned {
+                    self.chat.pin();
+                }
+                true
+            }
+            Msg::LogFilter(filter) => {
+                self.round.log.filter = filter;
+                true
+            }
+            Msg::Resync => match self.ws {
+                Some(ref mut task) => {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::Resync))));
+                    self.last_resync = now();
+                    false
+                }
+                None => false,
+            },
+            Msg::AnimationFrame => {
+                self.render_task = if self.animations.tick(now()) {
+                    Some(RenderService::request_animation_frame(
+                        self.link.callback(|_| Msg::AnimationFrame),
+                    ))
+                } else {
+                    None
+                };
+                true
+            }
+            Msg::TogglePlayerMute(username) => {
+                if !self.muted_players.remove(&username) {
+                    self.muted_players.insert(username);
+                }
+                true
+            }
+            Msg::ToggleBlock(username) => {
+                let identity = self.identity_of(&username);
+                let blocked = &mut self.settings.blocked;
+                match blocked.iter().position(|b| b.matches(&username, identity)) {
+                    Some(index) => {
+                        blocked.remove(index);
                     }
-                </div>
-
-                <div class="color-selector" style={format!("display: {}", if self.selecting {"flex"} else {"none"})}>
-                    <h1>{"Select the color you want to switch to"}</h1>
-                    <button onclick=self.link.callback(|_| Msg::SwitchColor("Yellow".to_string())) class="card" id="color" style="background-image: url(static/img/Selector.Yellow.svg"></button>
-                    <button onclick=self.link.callback(|_| Msg::SwitchColor("Red".to_string())) class="card" id="color" style="background-image: url(static/img/Selector.Red.svg"></button>
-                    <button onclick=self.link.callback(|_| Msg::SwitchColor("Blue".to_string())) class="card" id="color" style="background-image: url(static/img/Selector.Blue.svg"></button>
-                    <button onclick=self.link.callback(|_| Msg::SwitchColor("Green".to_string())) class="card" id="color" style="background-image: url(static/img/Selector.Green.svg"></button>
-                </div>
-
-                {
-                    if self.end_status.is_some() {
-                        let status = self.end_status.as_ref().unwrap();
+                    None => blocked.push(Block { username, identity }),
+                }
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::Unblock(index) => {
+                if index >= self.settings.blocked.len() {
+                    return false;
+                }
 
-                        // Get the game duration as seconds and format it to {min:s} format
-                        let duration = (status.statistics.end_time.unwrap().duration_since(status.statistics.start_time.unwrap())).unwrap().as_secs() as f64;
-                        let (minutes, seconds) = ((duration / 60.0).floor(), (((duration / 60.0) - (duration / 60.0).floor()) * 60.0).round());
+                self.settings.blocked.remove(index);
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::AccountInput(e) => {
+                self.account_input = e;
+                true
+            }
+            Msg::ClaimAccount => {
+                let name = self.account_input.trim().to_string();
+                if !identity::valid_account_name(&name) {
+                    self.account_error = Some(self.t_args(
+                        "account-name-invalid",
+                        &[("max", identity::MAX_ACCOUNT_NAME.into())],
+                    ));
+                    return true;
+                }
+
+                match self.ws {
+                    Some(ref mut task) => {
+                        // Confirmed by an IdentityUpdate, or refused with an Error
+                        task.send::<Text>(Text::into(Ok(to_json(PacketType::ClaimAccount(name)))));
+                        self.account_error = None;
+                        true
+                    }
+                    None => {
+                        self.account_error = Some(self.t("account-offline"));
+                        true
+                    }
+                }
+            }
+            Msg::WordFilterInput(e) => {
+                self.settings.word_filter = moderation::parse_words(&e);
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::SendEmote(emote) => match self.ws {
+                Some(_) if self.emote_limiter.try_send(now()).is_err() => false,
+                Some(ref mut task) => {
+                    task.send::<Text>(Text::into(Ok(to_json(PacketType::Emote(emote)))));
+                    self.show_emote(self.self_id(), emote);
+                    true
+                }
+                None => false,
+            },
+            Msg::EmoteExpired(id) => {
+                self.emote_tasks.remove(&id);
+                self.emotes.remove(&id).is_some()
+            }
+            Msg::ToggleEmotes => {
+                self.settings.emotes = !self.settings.emotes;
+                self.settings.save(&mut self.storage);
+                self.emotes.clear();
+                self.emote_tasks.clear();
+                true
+            }
+            Msg::ToggleMatchHistory => {
+                self.history_open = !self.history_open;
+                true
+            }
+            Msg::ClearMatchHistory => {
+                self.match_history = MatchHistory::default();
+                self.match_history.save(&mut self.storage);
+                true
+            }
+            Msg::ToggleLeaderboard => {
+                self.leaderboard_open = !self.leaderboard_open;
+                if self.leaderboard_open {
+                    self.fetch_leaderboard();
+                }
+                true
+            }
+            Msg::SelectLeaderboard(source) => {
+                self.leaderboard_source = source;
+                self.fetch_leaderboard();
+                true
+            }
+            Msg::ToggleSettings => {
+                self.settings_open = !self.settings_open;
+                true
+            }
+            Msg::ToggleMute => {
+                self.settings.muted = !self.settings.muted;
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::VolumeInput(e) => {
+                if let Ok(volume) = e.parse::<f64>() {
+                    self.settings.volume = (volume / 100.0).clamp(0.0, 1.0);
+                    self.settings.save(&mut self.storage);
+                }
+                true
+            }
+            Msg::PaletteInput(e) => {
+                if let Some(palette) = Palette::from_label(&e) {
+                    self.settings.palette = palette;
+                    self.settings.save(&mut self.storage);
+                }
+                true
+            }
+            Msg::ToggleColorGlyphs => {
+                self.settings.color_glyphs = !self.settings.color_glyphs;
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::LanguageInput(e) => {
+                if let Some(language) = Language::from_code(&e) {
+                    self.settings.language = language;
+                    self.settings.save(&mut self.storage);
+                    set_document_language(language);
+                }
+                true
+            }
+            Msg::ThemeInput(e) => {
+                self.settings.theme = e;
+                self.settings.save(&mut self.storage);
+                true
+            }
+            Msg::ThemeFile(file) => {
+                if let Some(file) = file {
+                    match ReaderService::read_file(file, self.link.callback(Msg::ThemeLoaded)) {
+                        Ok(task) => self.reader_task = Some(task),
+                        Err(e) => self.theme_error = Some(e.to_string()),
+                    }
+                }
+                true
+            }
+            Msg::ThemeLoaded(file) => {
+                self.reader_task = None;
+
+                let theme = String::from_utf8(file.content)
+                    .map_err(|e| theme::ThemeError::Malformed(e.to_string()))
+                    .and_then(|json| Theme::from_json(&json));
+
+                match theme {
+                    Ok(theme) => {
+                        self.settings.custom_themes.retain(|t| t.name != theme.name);
+                        self.settings.theme = theme.name.clone();
+                        self.settings.custom_themes.push(theme);
+                        self.settings.save(&mut self.storage);
+                        self.theme_error = None;
+                    }
+                    Err(e) => {
+                        self.theme_error = Some(format!(
+                            "{}: {}",
+                            file.name,
+                            e.describe(self.settings.language)
+                        ))
+                    }
+                }
+                true
+            }
+            Msg::KeyDown(e) => {
+                if !self.round.active || self.round.ended {
+                    return false;
+                }
+
+                let action = match keyboard::action(&e.key(), self.round.selecting) {
+                    Some(action) => action,
+                    None => return false,
+                };
+                // Arrow keys would scroll the page otherwise
+                e.prevent_default();
+
+                match action {
+                    KeyAction::Previous => {
+                        self.focused_card = self.focused_card.saturating_sub(1);
+                        true
+                    }
+                    KeyAction::Next => {
+                        self.focused_card =
+                            (self.focused_card + 1).min(self.cards.len().saturating_sub(1));
+                        true
+                    }
+                    KeyAction::Play => match self.cards.get(self.focused_card) {
+                        Some(card) if self.allowed_cards.contains(card) => {
+                            self.update(Msg::PlaceCard(card.clone()))
+                        }
+                        _ => false,
+                    },
+                    KeyAction::Draw => self.update(Msg::DrawCard),
+                    KeyAction::EndTurn => self.update(Msg::EndTurn),
+                    KeyAction::Color(color) => self.update(Msg::SwitchColor(color.to_string())),
+                }
+            }
+            Msg::SortStatistics(column) => {
+                let (current, descending) = self.statistics_sort;
+                self.statistics_sort = (column, current != column || !descending);
+                true
+            }
+            Msg::Error(e) => {
+                self.post(ServerMessage::Error(e.clone()));
+                ConsoleService::log(&e);
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, _prop: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        if let Some(element) = self.chat_ref.cast::<Element>() {
+            if let Some(anchor) = self.chat_anchor.take() {
+                // Keeps the messages in view from jumping when older ones are added above
+                element.set_scroll_top(element.scroll_height() - anchor);
+            } else if self.chat.pinned {
+                element.set_scroll_top(element.scroll_height());
+            }
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            // <div></div>
+            <div class="container" style=self.container_style()>
+                // login screen element
+                <div class="connect-screen" style={format!("display: {}", if !self.registered {"flex"} else {"none"})}>
+                    <h1>{self.t("connect-title")}</h1>
+                    <input type="text" placeholder=self.t("room-id-placeholder") value=self.room_id.clone() oninput=self.link.callback(|e: InputData| Msg::RoomIDInput(e.value))/><br/>
+                    <button disabled={self.room_id.is_none() ||self.room_id == Some("".to_string()) || self.queue.is_some()} onclick=self.link.callback(|_| Msg::Connect)>{self.t("connect")}</button>
+                    <button class="quick-play" hidden={self.ws.is_some() || self.queue.is_some()} onclick=self.link.callback(|_| Msg::QuickPlay)>{self.t("quick-play")}</button>
+                    {self.view_queue()}
+
+                    <h1 hidden={self.ws.is_none() && self.queue.is_none()}>{self.t("username-title")}</h1>
+                    <input hidden={self.ws.is_none() && self.queue.is_none()} type="text" placeholder=self.t("username-placeholder") value=self.username.clone() oninput=self.link.callback(|e: InputData| Msg::UsernameInput(e.value))/>
+                    <div class="profile-editor" hidden={self.ws.is_none()}>
+                        <ProfileAvatar username=self.username.clone().unwrap_or_default() profile=self.settings.profile.clone() size=64 />
+                        <div class="avatar-choices" role="radiogroup" aria-label=self.t("avatar")>
+                            <button role="radio" aria-checked=(self.settings.profile.avatar == Avatar::Initials).to_string()
+                                onclick=self.link.callback(|_| Msg::AvatarInput(Avatar::Initials))>
+                                {profile::initials(&self.username.clone().unwrap_or_default())}
+                            </button>
+                            {
+                                for profile::AVATARS.iter().enumerate().map(|(i, avatar)| html! {
+                                    <button role="radio" aria-checked=(self.settings.profile.avatar == Avatar::BuiltIn(i)).to_string()
+                                        onclick=self.link.callback(move |_| Msg::AvatarInput(Avatar::BuiltIn(i)))>
+                                        {avatar}
+                                    </button>
+                                })
+                            }
+                        </div>
+                        <ul>
+                            <a>{self.t("avatar-color")}</a>
+                            <input type="color" value=self.settings.profile.color(&self.username.clone().unwrap_or_default())
+                                onchange=self.link.callback(|e: ChangeData| match e {
+                                    ChangeData::Value(value) => Msg::ProfileColorInput(value),
+                                    _ => Msg::ProfileColorInput(String::new()),
+                                })/>
+                        </ul>
+                        <input type="text" maxlength=profile::MAX_BIO.to_string() placeholder=self.t("bio-placeholder")
+                            value=self.settings.profile.bio.clone()
+                            onchange=self.link.callback(|e: ChangeData| match e {
+                                ChangeData::Value(value) => Msg::BioInput(value),
+                                _ => Msg::BioInput(String::new()),
+                            })/>
+                    </div>
+                    <button hidden={self.ws.is_none()} disabled={self.username.is_none() || self.username == Some("".to_string())} onclick=self.link.callback(|_| Msg::Register)>{self.t("register")}</button>
+
+                    <ul class="connection-status"><a>{format!("{} ", self.t("connected"))}</a><a style={if self.ws.is_none() {"color: var(--red)"} else {"color: var(--green)"}}>{ if self.ws.is_some() {self.t("online")} else {self.t("offline")} }</a></ul>
+
+                </div>
+
+                <div class="waiting-screen" style={format!("display: {}", if self.registered && !self.round.active {"flex"} else {"none"})} >
+                    <h1>{if self.round.number > 1 {self.t_args("waiting-for-round", &[("round", self.round.number.into())])} else {self.t("waiting-for-game")}}</h1>
+                    <h2>{self.t("room-id")}</h2>
+                    <h3>{self.room_id.clone().unwrap_or_else(|| self.t("invalid-room-id"))}</h3>
+
+                    {
+                        if let Some(score) = &self.score {
+                            if score.rounds > 0 {
+                                html! { <Scoreboard score=score.clone() players=self.players() language=self.settings.language /> }
+                            } else {
+                                html! {}
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+
+                    <p hidden={!self.host}>{self.t("host")}</p>
+                    <input
+                        hidden={!self.host || self.round.number > 1}
+                        type="number"
+                        min="0"
+                        placeholder=self.t_args("score-target-placeholder", &[("target", Score::DEFAULT_TARGET.into())])
+                        value=self.options.score_target.map(|t| t.to_string()).unwrap_or_default()
+                        oninput=self.link.callback(|e: InputData| Msg::ScoreTargetInput(e.value))/>
+                    <button hidden={!self.host} disabled={!self.can_start()} onclick=self.link.callback(|_| Msg::StartGame)>{ if self.round.number > 1 {self.t("start-round")} else {self.t("start-game")} }</button>
+                </div>
+
+                <div class="cards-container" role="group" tabindex="0" aria-label=self.t("your-hand") aria-activedescendant=format!("card-{}", self.focused_card) style={format!("display: {}", if self.round.active {"flex"} else {"none"})} >
+                    { for self.cards.iter().enumerate().map(|(index, card)| {
+                        let c = card.clone();
+                        let playable = self.allowed_cards.contains(card);
+                        let lift = match &self.touch {
+                            Some(touch) if touch.index == index => touch.lift(),
+                            _ => 0.0,
+                        };
+                        // Not disabled, so unplayable cards can still be held down for a preview
+                        html! {
+                            <button
+                                id=format!("card-{}", index)
+                                class=format!("card {}{}", if playable {"allowed"} else {"disallowed"}, if index == self.focused_card {" focused"} else {""})
+                                style={format!("--fan: {:.1}deg; --lift: {:.0}px;", table::fan_angle(index, self.cards.len()), lift)}
+                                aria-label=self.t_args(if playable {"card-playable"} else {"card-not-playable"}, &[("card", self.card_name(card).into())])
+                                aria-disabled={(!playable).to_string()}
+                                onclick=self.link.batch_callback(move |_| if playable {Some(Msg::PlaceCard(c.clone()))} else {None})
+                                ontouchstart=self.link.batch_callback(move |e: TouchEvent| e.touches().get(0).map(|t| Msg::TouchStart(index, (t.client_x() as f64, t.client_y() as f64))))
+                                ontouchmove=self.link.batch_callback(|e: TouchEvent| e.touches().get(0).map(|t| Msg::TouchMove((t.client_x() as f64, t.client_y() as f64))))
+                                ontouchend=self.link.callback(|e: TouchEvent| {
+                                    e.prevent_default();
+                                    Msg::TouchEnd
+                                })
+                                ontouchcancel=self.link.callback(|_| Msg::TouchCancel) >
+                                {self.view_card(card)}
+                                {self.view_glyph(&card.color)}
+                            </button>
+                            }
+                        })
+                    }
+                    </div>
+                    <h2 hidden={!self.round.active} id="status-text"> { if self.round.turn && !self.round.selecting {self.t("your-turn")} else {self.t("waiting-for-opponent")} }</h2>
+                    <h1 id="place-card-text" class={if self.round.turn && !self.round.selecting && !self.allowed_cards.is_empty() {"your-turn"} else {""}}>
+                        <span class="pointer-hint">{self.t("place-card")}</span>
+                        <span class="touch-hint">{self.t("place-card-touch")}</span>
+                    </h1>
+                {
+                    match &self.preview {
+                        Some(card) => html! {
+                            <div class="card card-preview" aria-hidden="true">
+                                {self.view_card(card)}
+                                {self.view_glyph(&card.color)}
+                            </div>
+                        },
+                        None => html! {},
+                    }
+                }
+                // End turn button
+                <button onclick=self.link.callback(|_| Msg::EndTurn) aria-label=self.t("end-turn-label") class="end-turn-button" style={format!("display: {}", if self.round.active {"flex"} else {"none"})} ><h1>{self.t("end-turn")}</h1></button>
+
+                <div class="table" style={format!("display: {}", if self.round.active {"block"} else {"none"})}>
+                    <div class={format!("direction-arrow {}", table::direction_class(self.round.direction))}>
+                        {if self.round.direction == Direction::Clockwise {"↻"} else {"↺"}}
+                    </div>
+                    {
+                        for self.seats.iter().enumerate().map(|(index, id)| {
+                            let local = self.seats.iter().position(|s| s == &self.self_id()).unwrap_or(0);
+                            let (x, y) = table::seat_position(index, local, self.seats.len());
+
+                            let (username, card_count, turn) = match self.connections.get(id) {
+                                Some(player) => (player.username.clone(), player.card_count, player.turn),
+                                None => (self.display_username(), self.cards.len(), self.round.turn),
+                            };
+
+                            html! {
+                                <div class="seat" id={if turn {"seat-turn"} else {""}} style={format!("left: {:.2}%; top: {:.2}%;", x, y)}>
+                                    <h1>{username}</h1>
+                                    <h2>{card_count}</h2>
+                                </div>
+                            }
+                        })
+                    }
+                </div>
+
+                <div class="animation-layer">
+                {
+                    for self.animations.in_flight().map(|(animation, point)| {
+                        match &animation.card {
+                            Some(card) => html! {
+                                <div class="card flying-card" style={point.style()}>
+                                    {self.view_card(card)}
+                                </div>
+                            },
+                            None => html! {
+                                <div class="card flying-card" id="card-back" style={point.style()}></div>
+                            },
+                        }
+                    })
+                }
+                </div>
+
+                <div class={if self.round.turn {"deck-container your-turn"} else {"deck-container"}} style={format!("display: {}", if self.round.active {"flex"} else {"none"})}  >
+                    <button class="card" id="deck" aria-label=self.t("draw-card-label") onclick=self.link.callback(|_| Msg::DrawCard)><div class="logo"></div></button>
+                    <button class="card" id="deck"><div class="logo"></div></button>
+                    <button class="card" id="deck"><div class="logo"></div></button>
+                    <button class="card" id="deck"><div class="logo"></div></button>
+                    {
+                        match &self.round.current {
+                            Some(card) => html! {
+                                <div class="card" id="placed-deck" aria-label=self.card_name(card)>
+                                    {self.view_card(card)}
+                                    {self.view_glyph(&card.color)}
+                                </div>
+                            },
+                            None => html! {
+                                <div class="card" id="placed-deck" style="background-image: var(--card-back, url(static/img/Uno.svg));"></div>
+                            },
+                        }
+                    }
+                    <h1 class="draw-card-text">{self.t("draw-card")}</h1>
+                </div>
+
+                <div class="player-list">
+                    <button class="resync-button" hidden={!self.registered} onclick=self.link.callback(|_| Msg::Resync)>{self.t("resync")}</button>
+                    <div class="player-object" id="player-self" style={"order: -1;"}>
+                        {self.view_emote(&self.self_id())}
+                        <div class="player-detail">
+                            <ProfileAvatar username=self.display_username() profile=self.settings.profile.clone() />
+                        </div>
+                        <h2>{self.cards.len()}</h2>
+                        <h5 hidden={self.round.number <= 1}>{self.t_args("wins", &[("wins", (*self.wins.get(&self.self_id()).unwrap_or(&0)).into())])}</h5>
+                        <h1 style={if self.round.turn {"color: var(--green)"} else {"color: white"}}>
+                        {self.t_args("self-username", &[("username", self.username.clone().unwrap_or_else(|| self.t("unset")).into())])}
+                        </h1>
+                        {if self.round.next {html! {<h3>{self.t("next")}</h3>}} else if self.round.turn {html!{<h4>{self.t("turn")}</h4>}} else {html!{<h3></h3>}}}
+                    </div>
+                    <div class="emote-bar" hidden={!self.settings.emotes || !self.registered}>
+                    {
+                        for Emote::ALL.iter().map(|emote| {
+                            let emote = *emote;
+                            html! {
+                                <button onclick=self.link.callback(move |_| Msg::SendEmote(emote))>{emote.text(self.settings.language)}</button>
+                            }
+                        })
+                    }
+                    </div>
+
+                    {
+                        for self.connections.iter().map(|(id, player)| {
+                            let whisper = Channel::Whisper(*id);
+                            let (username, blocked) = (player.username.clone(), player.username.clone());
+
+                            html! {
+                                <div class="player-object" id="player-self" style={format!("order: {};", player.index)}>
+                                    {self.view_emote(id)}
+                                    <div class="player-detail">
+                                        <ProfileAvatar username=player.username.clone() profile=player.profile.clone() />
+                                    </div>
+                                    <h2>{player.card_count}</h2>
+                                    <h5 hidden={self.round.number <= 1}>{self.t_args("wins", &[("wins", (*self.wins.get(id).unwrap_or(&0)).into())])}</h5>
+                                    <h1
+                                    style={if player.turn {"color: var(--green)"} else {"color: white"}}
+                                    >
+                                    {&player.username}
+                                    {
+                                        match &player.account {
+                                            Some(account) => html! {<span class="account-badge" title=self.t_args("account-of", &[("account", account.as_str().into())])>{"✔"}</span>},
+                                            None => html! {},
+                                        }
+                                    }
+                                    </h1>
+                                    {if player.next {html! {<h3>{self.t("next")}</h3>}} else if player.turn {html!{<h4>{self.t("turn")}</h4>}} else {html!{<h3></h3>}}}
+                                    <button class="whisper-button" hidden={!self.can_write(whisper)}
+                                        aria-label=self.t_args("whisper-to", &[("username", player.username.as_str().into())])
+                                        onclick=self.link.callback(move |_| Msg::SelectChannel(whisper))>
+                                        {"@"}
+                                    </button>
+                                    <button class="moderation-button"
+                                        aria-pressed=self.muted_players.contains(&player.username).to_string()
+                                        aria-label=self.t_args("mute-player", &[("username", player.username.as_str().into())])
+                                        onclick=self.link.callback(move |_| Msg::TogglePlayerMute(username.clone()))>
+                                        {if self.muted_players.contains(&player.username) {"🔇"} else {"🔈"}}
+                                    </button>
+                                    <button class="moderation-button"
+                                        aria-pressed=self.is_blocked(&player.username).to_string()
+                                        aria-label=self.t_args("block-player", &[("username", player.username.as_str().into())])
+                                        onclick=self.link.callback(move |_| Msg::ToggleBlock(blocked.clone()))>
+                                        {"⛔"}
+                                    </button>
+                                    {
+                                        match player.identity {
+                                            Some(identity) if !self.friends.contains(&identity) => {
+                                                let username = player.username.clone();
+                                                html! {
+                                                    <button class="moderation-button"
+                                                        aria-label=self.t_args("add-friend", &[("username", player.username.as_str().into())])
+                                                        onclick=self.link.callback(move |_| Msg::AddFriend(identity, username.clone()))>
+                                                        {"+"}
+                                                    </button>
+                                                }
+                                            }
+                                            _ => html! {},
+                                        }
+                                    }
+                                </div>
+                            }
+                        })
+                    }
+                </div>
+
+                <div class="color-selector" style={format!("display: {}", if self.round.selecting {"flex"} else {"none"})}>
+                    <h1>{self.t("select-color")}</h1>
+                    {
+                        for keyboard::COLORS.iter().enumerate().map(|(i, color)| {
+                            html! {
+                                <button
+                                    onclick=self.link.callback(move |_| Msg::SwitchColor(color.to_string()))
+                                    aria-label=self.t_args("color-label", &[("color", i18n::color_name(self.settings.language, color).into()), ("key", (i + 1).into())])
+                                    class="card" id="color"
+                                    >
+                                    <CardFace card=Card { r#type: String::new(), color: color.to_string(), owner: None } colors=self.card_colors() />
+                                    {self.view_glyph(color)}
+                                </button>
+                            }
+                        })
+                    }
+                </div>
+
+                {
+                    if let Some(status) = &self.round.end_status {
+                        let duration = self.format_duration(game_duration(&status.statistics));
+                        let player_ids = self.player_ids();
 
                         html! {
-                            <div class="win-screen" style={format!("display: {}", if self.ended {"flex"} else {"none"})} >
-                                <h2>{"Game Ended"}</h2>
+                            <div class="win-screen" style={format!("display: {}", if self.round.ended {"flex"} else {"none"})} >
+                                <h2>{self.t("game-ended")}</h2>
 
-                                <ul><a>{"Game lasted "}</a><a class="highlight">{format!("{}min {}s", minutes, seconds)}</a></ul>
-                                <ul><a class="highlight">{status.statistics.player_count}</a><a>{" players took part"}</a></ul>
-                                <ul><a class="highlight">{status.statistics.spectator_count}</a><a>{" spectators took part"}</a></ul>
-                                <ul><a class="highlight">{status.statistics.cards_drawn}</a><a>{" cards were drawn"}</a></ul>
-                                <ul><a class="highlight">{status.statistics.cards_placed}</a><a>{" cards were placed"}</a></ul>
+                                <ul><a>{format!("{} ", self.t("game-lasted"))}</a><a class="highlight">{duration}</a></ul>
+                                <ul><a class="highlight">{status.statistics.player_count}</a><a>{format!(" {}", self.t_args("players-took-part", &[("n", status.statistics.player_count.into())]))}</a></ul>
+                                <ul><a class="highlight">{status.statistics.spectator_count}</a><a>{format!(" {}", self.t_args("spectators-took-part", &[("n", status.statistics.spectator_count.into())]))}</a></ul>
+                                <ul><a class="highlight">{status.statistics.cards_drawn}</a><a>{format!(" {}", self.t_args("cards-were-drawn", &[("n", status.statistics.cards_drawn.into())]))}</a></ul>
+                                <ul><a class="highlight">{status.statistics.cards_placed}</a><a>{format!(" {}", self.t_args("cards-were-placed", &[("n", status.statistics.cards_placed.into())]))}</a></ul>
 
-                                <h1>{format!("{} won", status.winner)}</h1>
+                                <div class="winner-profile">
+                                    <ProfileAvatar username=status.winner.clone() profile=self.profile_of(&status.winner) size=96 />
+                                    {
+                                        match self.profile_of(&status.winner).bio.as_str() {
+                                            "" => html! {},
+                                            bio => html! {<h5 class="bio">{bio}</h5>},
+                                        }
+                                    }
+                                </div>
+                                <h1>{self.t_args("winner", &[("username", status.winner.as_str().into())])}</h1>
+                                {
+                                    match self.rating_change {
+                                        Some((before, after)) => html! {
+                                            <h3 class="rating-change">{self.t_args("rating-change", &[
+                                                ("rating", (after.round() as usize).into()),
+                                                ("change", format!("{:+.0}", after - before).into()),
+                                            ])}</h3>
+                                        },
+                                        None => html! {},
+                                    }
+                                }
+                                {
+                                    if let Some(score) = &self.score {
+                                        html! {
+                                            <>
+                                                <h3>{
+                                                    match self.round_points {
+                                                        Some(points) => self.t_args("round-points", &[("points", points.into())]),
+                                                        None => self.t("round-points-unknown"),
+                                                    }
+                                                }</h3>
+                                                {
+                                                    if let Some(winner) = score.winner() {
+                                                        let username = self.players().into_iter().find(|(id, _)| id == &winner).map(|(_, u)| u).unwrap_or_default();
+                                                        html! { <h1>{self.t_args("match-winner", &[("username", username.into())])}</h1> }
+                                                    } else {
+                                                        html! {}
+                                                    }
+                                                }
+                                                <Scoreboard score=score.clone() players=self.players() language=self.settings.language />
+                                            </>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
                                 {
                                     for status.placements.iter().map(|username| {
                                         let index = status.placements.iter().position(|u| u == username).unwrap() + 2;
@@ -488,7 +1772,41 @@ impl Component for Model {
                                         }
                                     })
                                 }
-                                <button onclick=self.link.callback(|_| Msg::LeaveGame)>{"Continue"}</button>
+
+                                <table class="statistics-table">
+                                    <tr>
+                                    {
+                                        for StatisticsColumn::ALL.iter().map(|column| {
+                                            let c = *column;
+                                            let (sort, descending) = self.statistics_sort;
+                                            html! {
+                                                <th onclick=self.link.callback(move |_| Msg::SortStatistics(c))>
+                                                    {format!("{}{}", self.t(c.message_id()), if sort != c {""} else if descending {" ▼"} else {" ▲"})}
+                                                </th>
+                                            }
+                                        })
+                                    }
+                                    </tr>
+                                    {
+                                        for statistics::sorted(&status.statistics.players, self.statistics_sort.0, self.statistics_sort.1).iter().map(|player| {
+                                            html! {
+                                                <tr>
+                                                { for StatisticsColumn::ALL.iter().map(|column| html! { <td>{column.value(player)}</td> }) }
+                                                </tr>
+                                            }
+                                        })
+                                    }
+                                </table>
+                                <div class="statistics-export">
+                                    <a download="statistics.json" href=history::data_uri("application/json", &statistics::to_json(&status.statistics.players))>{self.t("export-json")}</a>
+                                    <a download="statistics.csv" href=history::data_uri("text/csv", &statistics::to_csv(&status.statistics.players))>{self.t("export-csv")}</a>
+                                </div>
+                                <h5>{self.t_args("rematch-votes", &[("votes", self.round.rematch_votes.count(&player_ids).into()), ("players", player_ids.len().into())])}</h5>
+                                <div class="win-screen-buttons">
+                                    <button disabled={self.round.rematch_votes.contains(&self.self_id())} onclick=self.link.callback(|_| Msg::Rematch)>{self.t("rematch")}</button>
+                                    <button hidden={!self.host} disabled={!self.can_start()} onclick=self.link.callback(|_| Msg::StartGame)>{self.t("start-round")}</button>
+                                    <button onclick=self.link.callback(|_| Msg::LeaveGame)>{self.t("leave")}</button>
+                                </div>
                             </div>
                         }
                     }
@@ -497,58 +1815,1200 @@ impl Component for Model {
                     }
                 }
 
-                <div class="chat">
-                {
-                    for self.chat.iter().map(|message| {
-
-                        match message {
-                            ServerMessage::Join(username) => {
-                                html! {
-                                    <div class="chat-object">
-                                        <h3 id="connection">{"[Connected]"}</h3>
-                                        <h2>{username}</h2>
-                                    </div>
-                                }
-                            },
-                            ServerMessage::Leave(username) => {
-                                html! {
-                                    <div class="chat-object">
-                                    <h3 id="connection">{"[Disconnected]"}</h3>
-                                    <h2>{username}</h2>
+                <div class="game-log" style={format!("display: {}", if self.round.active {"flex"} else {"none"})}>
+                    <div class="game-log-filters">
+                    {
+                        for EventFilter::ALL.iter().map(|filter| {
+                            let f = *filter;
+                            html! {
+                                <button
+                                    id={if self.round.log.filter == f {"selected"} else {""}}
+                                    onclick=self.link.callback(move |_| Msg::LogFilter(f))>
+                                    {self.t(f.message_id())}
+                                </button>
+                            }
+                        })
+                    }
+                        <a class="game-log-export" download="game-log.txt" href=self.round.log.to_data_uri(self.settings.language)>{self.t("log-export")}</a>
+                    </div>
+                    <div class="game-log-events">
+                    {
+                        for self.round.log.filtered().rev().map(|(i, event)| {
+                            html! {
+                                <div class="game-log-object">
+                                    <h3>{format!("{}.", i + 1)}</h3>
+                                    <h1>{event.describe(self.settings.language)}</h1>
                                 </div>
-                                }
-                            },
-                            ServerMessage::Message(username, content) => {
+                            }
+                        })
+                    }
+                    </div>
+                </div>
+
+                <div class="sr-only" aria-live="polite">{&self.announcement}</div>
+
+                <div class="toolbar">
+                    <button onclick=self.link.callback(|_| Msg::ToggleMute)>{if self.settings.muted {self.t("unmute")} else {self.t("mute")}}</button>
+                    <button onclick=self.link.callback(|_| Msg::ToggleMatchHistory)>{self.t("history")}</button>
+                    <button onclick=self.link.callback(|_| Msg::ToggleLeaderboard)>{self.t("leaderboard")}</button>
+                    <button onclick=self.link.callback(|_| Msg::ToggleFriends)>
+                        {self.t("friends")}
+                        {
+                            if self.friends.invites.is_empty() {
+                                html! {}
+                            } else {
+                                html! {<span class="unread-badge">{self.friends.invites.len()}</span>}
+                            }
+                        }
+                    </button>
+                    <button onclick=self.link.callback(|_| Msg::ToggleSettings)>{self.t("settings")}</button>
+                </div>
+
+                {self.view_match_history()}
+                {self.view_leaderboard()}
+                {self.view_friends()}
+                {self.view_invites()}
+
+                <div class="settings" style={format!("display: {}", if self.settings_open {"flex"} else {"none"})}>
+                    <h1>{self.t("settings")}</h1>
+                    <ul>
+                        <a>{self.t("language")}</a>
+                        <select onchange=self.link.callback(|e: ChangeData| match e {
+                            ChangeData::Select(select) => Msg::LanguageInput(select.value()),
+                            _ => Msg::LanguageInput(String::new()),
+                        })>
+                        {
+                            for Language::ALL.iter().map(|language| html! {
+                                <option value=language.code() selected={language == &self.settings.language}>{language.label()}</option>
+                            })
+                        }
+                        </select>
+                    </ul>
+                    <ul>
+                        <a>{self.t("volume")}</a>
+                        <input type="range" min="0" max="100"
+                            value=((self.settings.volume * 100.0).round() as i64).to_string()
+                            oninput=self.link.callback(|e: InputData| Msg::VolumeInput(e.value))/>
+                    </ul>
+                    <ul>
+                        <a>{self.t("muted")}</a>
+                        <input type="checkbox" checked=self.settings.muted onclick=self.link.callback(|_| Msg::ToggleMute)/>
+                    </ul>
+                    <ul>
+                        <a>{self.t("palette")}</a>
+                        <select onchange=self.link.callback(|e: ChangeData| match e {
+                            ChangeData::Select(select) => Msg::PaletteInput(select.value()),
+                            _ => Msg::PaletteInput(String::new()),
+                        })>
+                        {
+                            for Palette::ALL.iter().map(|palette| html! {
+                                <option value=palette.label() selected={palette == &self.settings.palette}>{self.t(&format!("palette-{}", palette.label().to_lowercase()))}</option>
+                            })
+                        }
+                        </select>
+                    </ul>
+                    <ul>
+                        <a>{self.t("theme")}</a>
+                        <select onchange=self.link.callback(|e: ChangeData| match e {
+                            ChangeData::Select(select) => Msg::ThemeInput(select.value()),
+                            _ => Msg::ThemeInput(String::new()),
+                        })>
+                        {
+                            for self.themes.iter().chain(self.settings.custom_themes.iter()).map(|theme| html! {
+                                <option value=theme.name.clone() selected={theme.name == self.theme().name}>{&theme.name}</option>
+                            })
+                        }
+                        </select>
+                    </ul>
+                    <ul>
+                        <a>{self.t("load-theme")}</a>
+                        <input type="file" accept=".json,application/json" onchange=self.link.callback(|e: ChangeData| match e {
+                            ChangeData::Files(files) => Msg::ThemeFile(files.get(0)),
+                            _ => Msg::ThemeFile(None),
+                        })/>
+                    </ul>
+                    {
+                        match &self.theme_error {
+                            Some(e) => html! {<h5 class="theme-error">{self.t_args("theme-rejected", &[("error", e.as_str().into())])}</h5>},
+                            None => html! {},
+                        }
+                    }
+                    <ul>
+                        <a>{self.t("color-glyphs")}</a>
+                        <input type="checkbox" checked=self.settings.color_glyphs onclick=self.link.callback(|_| Msg::ToggleColorGlyphs)/>
+                    </ul>
+                    <ul>
+                        <a>{self.t("show-emotes")}</a>
+                        <input type="checkbox" checked=self.settings.emotes onclick=self.link.callback(|_| Msg::ToggleEmotes)/>
+                    </ul>
+                    <ul>
+                        <a>{self.t("word-filter")}</a>
+                        <input type="text" placeholder=self.t("word-filter-hint")
+                            value=self.settings.word_filter.join(", ")
+                            onchange=self.link.callback(|e: ChangeData| match e {
+                                ChangeData::Value(value) => Msg::WordFilterInput(value),
+                                _ => Msg::WordFilterInput(String::new()),
+                            })/>
+                    </ul>
+                    <ul class="blocked-players">
+                        <a>{self.t("blocked-players")}</a>
+                        {
+                            if self.settings.blocked.is_empty() {
+                                html! {<h5>{self.t("no-blocked-players")}</h5>}
+                            } else {
                                 html! {
-                                    <div class="chat-object">
-                                        <h3 id="message">{"[Message]"}</h3>
-                                        <h2>{username}</h2>
-                                        <h1>{content}</h1>
-                                    </div>
+                                    for self.settings.blocked.iter().enumerate().map(|(index, block)| {
+                                        html! {
+                                            <button onclick=self.link.callback(move |_| Msg::Unblock(index))>
+                                                {self.t_args("unblock-player", &[("username", block.username.as_str().into())])}
+                                            </button>
+                                        }
+                                    })
                                 }
+                            }
+                        }
+                    </ul>
+                    <ul class="account">
+                        <a>{self.t("identity")}</a>
+                        <h5 title=self.identity.identity.to_string()>{Identity::short(&self.identity.identity)}</h5>
+                    </ul>
+                    {
+                        match &self.identity.account {
+                            Some(account) => html! {
+                                <ul class="account"><a>{self.t("account")}</a><h5>{account}</h5></ul>
                             },
-                            ServerMessage::Error(body) => {
-                                html! {
-                                    <div class="chat-object">
-                                        <h3 id="error">{"[Error]"}</h3>
-                                        <h2>{"Server"}</h2>
-                                        <h1>{body}</h1>
-                                    </div>
-                                }
+                            None => html! {
+                                <ul class="account">
+                                    <input type="text" maxlength=identity::MAX_ACCOUNT_NAME.to_string() placeholder=self.t("account-placeholder")
+                                        value=self.account_input.clone()
+                                        oninput=self.link.callback(|e: InputData| Msg::AccountInput(e.value))/>
+                                    <button disabled=self.ws.is_none() onclick=self.link.callback(|_| Msg::ClaimAccount)>{self.t("claim-account")}</button>
+                                </ul>
                             },
                         }
-                    })
-                }
-                    <div class="chat-input">
-                            <input type="text" placeholder="Send a message to chat" value=self.chat_input.clone() oninput=self.link.callback(|e: InputData| Msg::ChatInput(e.value))/>
-                            <button type="submit" onclick=self.link.callback(|_| Msg::sendMessage)>{"Send"}</button>
-                    </div>
+                    }
+                    {
+                        match &self.account_error {
+                            Some(e) => html! {<h5 class="theme-error">{e}</h5>},
+                            None => html! {},
+                        }
+                    }
+                    <h5>{self.t("keys-help")}</h5>
+                    <button onclick=self.link.callback(|_| Msg::ToggleSettings)>{self.t("close")}</button>
+                </div>
+
+                <button class={if self.chat_open {"chat-toggle open"} else {"chat-toggle"}} onclick=self.link.callback(|_| Msg::ToggleChat)>
+                    {if self.chat_open {self.t("chat-hide")} else {self.t("chat-show")}}
+                    {
+                        if self.chat.unread_total() > 0 {
+                            html! {<span class="unread-badge">{self.chat.unread_total()}</span>}
+                        } else {
+                            html! {}
+                        }
+                    }
+                </button>
+                <div class={if self.chat_open {"chat open"} else {"chat"}}>
+                    <div class="chat-tabs" role="tablist">
+                    {
+                        for self.chat_tabs().into_iter().map(|channel| {
+                                let unread = self.chat.unread(channel);
+                                html! {
+                                    <button role="tab" aria-selected={(channel == self.chat.channel).to_string()}
+                                        class={if channel == self.chat.channel {"chat-tab selected"} else {"chat-tab"}}
+                                        onclick=self.link.callback(move |_| Msg::SelectChannel(channel))>
+                                        {self.channel_label(channel)}
+                                        {if unread > 0 {html! {<span class="unread-badge">{unread}</span>}} else {html! {}}}
+                                    </button>
+                                }
+                            })
+                    }
+                    </div>
+                    <div class="chat-messages" ref=self.chat_ref.clone() onscroll=self.link.callback(|_| Msg::ChatScroll)>
+                        <button class="chat-older" hidden={!self.chat.has_older()} onclick=self.link.callback(|_| Msg::LoadOlderMessages)>{self.t("chat-older")}</button>
+                        {
+                            for self.chat.visible()
+                                .filter(|entry| !matches!(&entry.message, ServerMessage::Message(sender, _) if self.silenced(sender)))
+                                .map(|entry| self.view_chat_entry(entry))
+                        }
+                    </div>
+                    {
+                        if !self.chat.pinned && self.chat.unread(self.chat.channel) > 0 {
+                            html! {
+                                <button class="chat-jump" onclick=self.link.callback(|_| Msg::JumpToLatest)>
+                                    {self.t_args("unread-messages", &[("n", self.chat.unread(self.chat.channel).into())])}
+                                </button>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                <div class="chat-input">
+                            <input type="text" value=self.chat_input.clone()
+                                disabled=!self.can_write(self.chat.channel)
+                                placeholder={if self.can_write(self.chat.channel) {self.t("chat-placeholder")} else {self.t("chat-read-only")}}
+                                oninput=self.link.callback(|e: InputData| Msg::ChatInput(e.value))
+                                onkeydown=self.link.batch_callback(|e: KeyboardEvent| if e.key() == "Enter" {Some(Msg::SendMessage)} else {None})/>
+                            <button type="submit" onclick=self.link.callback(|_| Msg::SendMessage)>{self.t("send")}</button>
+                    </div>
+                </div>
+
+            </div>
+
+        }
+    }
+}
+
+impl Model {
+    fn t(&self, id: &str) -> String {
+        i18n::tr(self.settings.language, id)
+    }
+
+    fn t_args(&self, id: &str, args: &[(&str, Arg)]) -> String {
+        i18n::format(self.settings.language, id, args)
+    }
+
+    fn card_name(&self, card: &Card) -> String {
+        i18n::card_name(self.settings.language, card)
+    }
+
+    fn display_username(&self) -> String {
+        self.username.clone().unwrap_or_else(|| self.t("you"))
+    }
+
+    fn self_id(&self) -> Uuid {
+        self.id.unwrap_or_else(Uuid::nil)
+    }
+
+    // Called for every status update, a new round starts when the previous one has ended
+    fn start_round(&mut self) {
+        if self.round.ended {
+            self.reset_round();
+        }
+
+        if !self.round.active {
+            self.round.active = true;
+            self.round.rematch_votes.clear();
+        }
+    }
+
+    // Clears the hand and turn state, keeping the room, chat and wins
+    fn reset_round(&mut self) {
+        self.round.reset();
+        self.reset_points();
+
+        self.set_cards(Vec::new());
+        self.allowed_cards = Vec::new();
+
+        self.connections.values_mut().for_each(|p| {
+            p.card_count = 0;
+            p.turn = false;
+            p.next = false;
+        });
+    }
+
+    // A finished match starts over with the same target
+    fn reset_points(&mut self) {
+        if let Some(score) = &self.score {
+            if score.is_finished() {
+                self.score = Some(Score::new(score.target));
+            }
+        }
+        self.round_points = None;
+    }
+
+    // Options can't change in the middle of a match
+    fn apply_options(&mut self, options: GameOptions) {
+        let playing = self
+            .score
+            .as_ref()
+            .is_some_and(|s| s.rounds > 0 && !s.is_finished());
+
+        if !playing {
+            self.score = options.score_target.map(Score::new);
+            self.options = options;
+        }
+    }
+
+    // Replaces everything known about the room, the snapshot is authoritative
+    fn apply_snapshot(&mut self, snapshot: StateSnapshot) {
+        let id = self.self_id();
+
+        // Spectators stay connected, they just aren't seated
+        let mut connections = HashMap::new();
+        snapshot
+            .seats
+            .iter()
+            .chain(snapshot.spectators.iter())
+            .filter(|seat| seat.id != id)
+            .for_each(|seat| {
+                let mut player = Player::new(seat.username.clone(), seat.card_count, 0);
+                // Profiles and identities are only sent on joining, the snapshot doesn't carry them
+                if let Some(previous) = self.connections.get(&seat.id) {
+                    player.profile = previous.profile.clone();
+                    player.identity = previous.identity;
+                    player.account = previous.account.clone();
+                }
+                player.turn = snapshot.turn == Some(seat.id);
+                player.next = snapshot.next == Some(seat.id);
+                connections.insert(seat.id, player);
+            });
+
+        self.connections = connections;
+        self.seats = snapshot.seats.iter().map(|seat| seat.id).collect();
+        self.reindex();
+
+        self.round.apply_snapshot(id, &snapshot);
+        self.reset_points();
+        self.set_cards(snapshot.cards);
+        self.allowed_cards = snapshot.allowed_cards;
+
+        if self.options != snapshot.options {
+            self.score = snapshot.options.score_target.map(Score::new);
+            self.options = snapshot.options;
+        }
+    }
+
+    fn animate(&mut self, cards: Vec<Option<Card>>, from: Point, to: Point) {
+        if cards.is_empty() {
+            return;
+        }
+
+        self.animations.push(cards, from, to, now());
+
+        if self.render_task.is_none() {
+            self.render_task = Some(RenderService::request_animation_frame(
+                self.link.callback(|_| Msg::AnimationFrame),
+            ));
+        }
+    }
+
+    fn seat_point(&self, id: &Uuid) -> Point {
+        let local = self
+            .seats
+            .iter()
+            .position(|s| s == &self.self_id())
+            .unwrap_or(0);
+        let seat = self.seats.iter().position(|s| s == id).unwrap_or(0);
+
+        Point::seat(table::seat_position(seat, local, self.seats.len()))
+    }
+
+    // Players not in the seat order yet sit last
+    fn seat(&mut self, id: Uuid) {
+        if !self.seats.contains(&id) {
+            self.seats.push(id);
+        }
+    }
+
+    // Keeps the keyboard focus on a card still in hand
+    fn set_cards(&mut self, cards: Vec<Card>) {
+        self.cards = cards;
+        self.focused_card = self.focused_card.min(self.cards.len().saturating_sub(1));
+    }
+
+    fn reindex(&mut self) {
+        let seats = &self.seats;
+
+        self.connections.iter_mut().for_each(|(id, player)| {
+            player.index = seats.iter().position(|s| s == id).unwrap_or(seats.len());
+        });
+    }
+
+    fn packet_context(&self) -> PacketContext<'_> {
+        PacketContext {
+            id: self.id,
+            connections: &self.connections,
+            cards: &self.cards,
+        }
+    }
+
+    // Rejected packets are dropped and the server is asked for the full state, at most every few seconds
+    fn reject_packet(&mut self, error: PacketError, packet: &str) {
+        ConsoleService::error(&format!(
+            "Rejected packet: {:?}. Packet: {}",
+            error,
+            packet.chars().take(500).collect::<String>()
+        ));
+
+        let error = error.describe(self.settings.language);
+        let warning = self.t_args("invalid-update", &[("error", error.into())]);
+        self.post(ServerMessage::Warning(warning));
+
+        if now() - self.last_resync < RESYNC_INTERVAL {
+            return;
+        }
+
+        if let Some(ref mut task) = self.ws {
+            ConsoleService::log("Requesting a resync from the server");
+            task.send::<Text>(Text::into(Ok(to_json(PacketType::Resync))));
+            self.last_resync = now();
+        }
+    }
+
+    // After a round has ended, everyone in the room has to vote for the next one
+    fn can_start(&self) -> bool {
+        self.seats.len() > 1
+            && (!self.round.ended || self.round.rematch_votes.ready(&self.player_ids()))
+    }
+
+    fn player_ids(&self) -> Vec<Uuid> {
+        self.players().into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn players(&self) -> Vec<(Uuid, String)> {
+        let mut players: Vec<(Uuid, String)> = self
+            .connections
+            .iter()
+            .map(|(id, p)| (*id, p.username.clone()))
+            .collect();
+        players.push((self.self_id(), self.display_username()));
+
+        players
+    }
+
+    // The selected theme, falls back to the first built-in one if it has been removed
+    fn theme(&self) -> &Theme {
+        self.themes
+            .iter()
+            .chain(self.settings.custom_themes.iter())
+            .find(|t| t.name == self.settings.theme)
+            .or_else(|| self.themes.first())
+            .expect("built-in themes are valid")
+    }
+
+    // A colorblind palette takes precedence over the theme's card colors
+    fn card_colors(&self) -> [String; 4] {
+        match self.settings.palette {
+            Palette::Default => self.theme().card_colors(),
+            palette => palette.colors().map(String::from),
+        }
+    }
+
+    fn container_style(&self) -> String {
+        match self.settings.palette {
+            Palette::Default => self.theme().css_variables(),
+            palette => format!(
+                "{} {}",
+                self.theme().css_variables(),
+                palette.css_variables()
+            ),
+        }
+    }
+
+    fn view_card(&self, card: &Card) -> Html {
+        match self.theme().art(card) {
+            Some(url) => html! {
+                <img class="card-face" src=url.to_string() alt="" />
+            },
+            None => html! {
+                <CardFace card=card.clone() colors=self.card_colors() />
+            },
+        }
+    }
+
+    fn view_glyph(&self, color: &str) -> Html {
+        if !self.settings.color_glyphs {
+            return html! {};
+        }
+
+        html! {
+            <span class="color-glyph" style={format!("color: var(--{});", color.to_lowercase())}>
+                {palette::glyph(color)}
+            </span>
+        }
+    }
+
+    fn post(&mut self, message: ServerMessage) {
+        self.post_to(None, message, Channel::Room);
+    }
+
+    // Adds a message to the chat, pinging on mentions and counting it as unread when it can't be seen
+    fn post_to(&mut self, id: Option<Uuid>, message: ServerMessage, channel: Channel) {
+        if matches!(&message, ServerMessage::Message(sender, _) if self.silenced(sender)) {
+            return;
+        }
+
+        let own = self.username.clone().unwrap_or_default();
+        let mention = match &message {
+            ServerMessage::Message(sender, content) if sender != &own => {
+                let mention = chat::mentions(content, &own);
+                self.play(if mention { Cue::Mention } else { Cue::Chat });
+                mention
+            }
+            _ => false,
+        };
+
+        if !self.chat.pinned || !self.chat_visible() || self.chat.channel != channel {
+            *self.chat.unread.entry(channel).or_insert(0) += 1;
+        }
+
+        let offset = js_sys::Date::new_0().get_timezone_offset();
+        self.chat.push(
+            id,
+            message,
+            channel,
+            chat::local_time(now(), offset),
+            mention,
+        );
+    }
+
+    // Replaces any bubble already over the player, restarting its timer
+    fn show_emote(&mut self, id: Uuid, emote: Emote) {
+        self.emotes.insert(id, emote);
+        self.emote_tasks.insert(
+            id,
+            TimeoutService::spawn(
+                Duration::from_millis(emote::BUBBLE_DURATION),
+                self.link.callback(move |_| Msg::EmoteExpired(id)),
+            ),
+        );
+    }
+
+    fn view_emote(&self, id: &Uuid) -> Html {
+        match self.emotes.get(id) {
+            Some(emote) => html! {
+                <div class="emote-bubble" role="status">{emote.text(self.settings.language)}</div>
+            },
+            None => html! {},
+        }
+    }
+
+    // Profiles are looked up by username, as that's all chat messages and the win screen have
+    fn profile_of(&self, username: &str) -> Profile {
+        if self.username.as_deref() == Some(username) {
+            return self.settings.profile.clone();
+        }
+
+        self.connections
+            .values()
+            .find(|player| player.username == username)
+            .map(|player| player.profile.clone())
+            .unwrap_or_default()
+    }
+
+    // Friends see the account name, or the username registered with.
+    // Before either is known the server is only told who to follow.
+    fn send_presence(&mut self) {
+        let username = self
+            .identity
+            .account
+            .clone()
+            .or_else(|| self.username.clone().filter(|u| !u.trim().is_empty()));
+
+        if let Some(ref mut task) = self.presence_ws {
+            task.send::<Text>(Text::into(Ok(to_json(PacketType::PresenceHello(
+                self.identity.clone(),
+                username,
+                self.friends.identities(),
+            )))));
+        }
+    }
+
+    // The presence server only reports on the friends it's been told about
+    fn follow_friends(&mut self) {
+        if let Some(ref mut task) = self.presence_ws {
+            task.send::<Text>(Text::into(Ok(to_json(PacketType::PresenceFollow(
+                self.friends.identities(),
+            )))));
+        }
+    }
+
+    fn view_friends(&self) -> Html {
+        let room = self.room_id.clone().filter(|_| self.registered);
+
+        html! {
+            <div class="match-history friends" style={format!("display: {}", if self.friends_open {"flex"} else {"none"})}>
+                <h1>{self.t("friends")}</h1>
+                {
+                    if self.presence_ws.is_none() {
+                        html! {<h5>{self.t("presence-offline")}</h5>}
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if self.friends.friends.is_empty() {
+                        html! {<h5>{self.t("no-friends")}</h5>}
+                    } else {
+                        html! {
+                            <table class="history-table">
+                            {
+                                for self.friends.sorted().into_iter().map(|friend| {
+                                    let identity = friend.identity;
+                                    let presence = self.friends.presence(&identity);
+                                    let status = match &presence {
+                                        Presence::Offline => self.t("presence-away"),
+                                        Presence::Online => self.t("presence-online"),
+                                        Presence::InRoom(r) if Some(r) == room.as_ref() => self.t("presence-here"),
+                                        Presence::InRoom(_) => self.t("presence-in-room"),
+                                    };
+                                    let invitable = room.is_some()
+                                        && presence != Presence::Offline
+                                        && !matches!(&presence, Presence::InRoom(r) if Some(r) == room.as_ref());
+
+                                    html! {
+                                        <tr>
+                                            <td><span class={if presence == Presence::Offline {"presence"} else {"presence online"}}></span>{&friend.username}</td>
+                                            <td>{status}</td>
+                                            <td>
+                                                <button disabled=!invitable onclick=self.link.callback(move |_| Msg::InviteFriend(identity))>{self.t("invite")}</button>
+                                                <button onclick=self.link.callback(move |_| Msg::RemoveFriend(identity))>{self.t("remove-friend")}</button>
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                            }
+                            </table>
+                        }
+                    }
+                }
+                <button onclick=self.link.callback(|_| Msg::ToggleFriends)>{self.t("close")}</button>
+            </div>
+        }
+    }
+
+    fn view_invites(&self) -> Html {
+        html! {
+            <div class="invites" aria-live="polite">
+            {
+                for self.friends.invites.iter().map(|invite| {
+                    let identity = invite.identity;
+                    html! {
+                        <div class="invite">
+                            <h3>{self.t_args("invite-received", &[("username", invite.username.as_str().into())])}</h3>
+                            <button onclick=self.link.callback(move |_| Msg::AcceptInvite(identity))>{self.t("join")}</button>
+                            <button onclick=self.link.callback(move |_| Msg::DismissInvite(identity))>{self.t("dismiss")}</button>
+                        </div>
+                    }
+                })
+            }
+            </div>
+        }
+    }
+
+    fn leave_queue(&mut self) {
+        self.queue = None;
+        self.queue_tick = None;
+        self.queue_ws = None;
+    }
+
+    fn view_queue(&self) -> Html {
+        let queue = match &self.queue {
+            Some(queue) => queue,
+            None => return html! {},
+        };
+        let time = now();
+
+        html! {
+            <div class="queue-status" role="status">
+                <h2>{self.t("queue-searching")}</h2>
+                {
+                    match queue.position {
+                        Some(position) => html! {<h3>{self.t_args("queue-position", &[("position", position.into())])}</h3>},
+                        None => html! {},
+                    }
+                }
+                <h3>
+                {
+                    match queue.remaining(time) {
+                        Some(seconds) => self.t_args("queue-estimate", &[("time", self.format_duration(Some(seconds)).into())]),
+                        None => self.t("queue-estimating"),
+                    }
+                }
+                </h3>
+                <h5>{self.t_args("queue-waited", &[("time", self.format_duration(Some(queue.waited(time))).into())])}</h5>
+                <button onclick=self.link.callback(|_| Msg::CancelQueue)>{self.t("cancel")}</button>
+            </div>
+        }
+    }
+
+    // Stores the finished game for the history page
+    fn record_match(
+        &mut self,
+        winner_id: Uuid,
+        winner: &str,
+        placements: &VecDeque<String>,
+        statistics: &GameStatistics,
+    ) {
+        let id = self.self_id();
+        let username = self.display_username();
+        let own = statistics.players.iter().find(|p| p.id == id);
+        let placements: Vec<String> = placements.iter().cloned().collect();
+
+        let opponents = self
+            .connections
+            .iter()
+            .filter(|(id, _)| self.seats.contains(id))
+            .map(|(_, player)| Opponent {
+                username: player.username.clone(),
+                identity: player.identity,
+            })
+            .collect();
+
+        self.rating_change = None;
+        let placement = match_history::placement(
+            &username,
+            winner_id == id,
+            &placements,
+            self.seats.contains(&id),
+            statistics.player_count.max(self.seats.len()),
+        );
+        if placement.is_some() {
+            self.record_ratings(winner, &placements);
+        }
+
+        self.match_history.push(MatchRecord {
+            finished: now(),
+            duration: game_duration(statistics),
+            room: self.room_id.clone(),
+            placement,
+            player_count: statistics.player_count.max(self.seats.len()),
+            winner: winner.to_string(),
+            opponents,
+            cards_placed: own.map(|p| p.cards_placed).unwrap_or(0),
+            cards_drawn: own.map(|p| p.cards_drawn).unwrap_or(0),
+        });
+        self.match_history.save(&mut self.storage);
+    }
+
+    // Local ratings only see the games played on this device, the server's are used when it has them
+    fn record_ratings(&mut self, winner: &str, placements: &[String]) {
+        let own = (self.display_username(), Some(self.identity.identity));
+        let mut seated: Vec<(String, Option<Uuid>)> = self
+            .connections
+            .iter()
+            .filter(|(id, _)| self.seats.contains(id))
+            .map(|(_, player)| (player.username.clone(), player.identity))
+            .chain(std::iter::once(own.clone()))
+            .collect();
+
+        // Finishing order, anyone left out of the placements finished last
+        let mut order = Vec::new();
+        std::iter::once(winner)
+            .chain(placements.iter().map(String::as_str))
+            .for_each(|username| {
+                if let Some(index) = seated.iter().position(|(u, _)| u == username) {
+                    order.push(seated.remove(index));
+                }
+            });
+        order.extend(seated);
+
+        let before = self.ratings.rating(&own.0, own.1);
+        self.ratings.record(&order);
+        self.ratings.save(&mut self.storage);
+        self.rating_change = Some((before, self.ratings.rating(&own.0, own.1)));
+    }
+
+    fn fetch_leaderboard(&mut self) {
+        if self.leaderboard_source != LeaderboardSource::Server {
+            return;
+        }
+
+        if let Some(ref mut task) = self.ws {
+            task.send::<Text>(Text::into(Ok(to_json(PacketType::FetchLeaderboard(
+                rating::LEADERBOARD_SIZE,
+            )))));
+        }
+    }
+
+    fn view_leaderboard(&self) -> Html {
+        let (entries, note) = match (self.leaderboard_source, &self.server_leaderboard) {
+            (LeaderboardSource::Local, _) => (self.ratings.leaderboard(), None),
+            (LeaderboardSource::Server, Some(entries)) => (entries.clone(), None),
+            (LeaderboardSource::Server, None) if self.ws.is_none() => (
+                self.ratings.leaderboard(),
+                Some(self.t("leaderboard-offline")),
+            ),
+            (LeaderboardSource::Server, None) => (Vec::new(), Some(self.t("leaderboard-loading"))),
+        };
+
+        let tab = |source: LeaderboardSource, label: &str| {
+            html! {
+                <button role="tab" aria-selected=(self.leaderboard_source == source).to_string()
+                    class={if self.leaderboard_source == source {"chat-tab selected"} else {"chat-tab"}}
+                    onclick=self.link.callback(move |_| Msg::SelectLeaderboard(source))>
+                    {self.t(label)}
+                </button>
+            }
+        };
+
+        html! {
+            <div class="match-history leaderboard" style={format!("display: {}", if self.leaderboard_open {"flex"} else {"none"})}>
+                <h1>{self.t("leaderboard")}</h1>
+                <div class="chat-tabs" role="tablist">
+                    {tab(LeaderboardSource::Server, "leaderboard-server")}
+                    {tab(LeaderboardSource::Local, "leaderboard-local")}
                 </div>
+                {
+                    match note {
+                        Some(note) => html! {<h5>{note}</h5>},
+                        None => html! {},
+                    }
+                }
+                <table class="history-table">
+                    <tr>
+                        <th>{"#"}</th>
+                        <th>{self.t("stats-player")}</th>
+                        <th>{self.t("rating")}</th>
+                        <th>{self.t("lifetime-games")}</th>
+                        <th>{self.t("lifetime-wins")}</th>
+                    </tr>
+                    {
+                        for entries.iter().enumerate().map(|(i, entry)| {
+                            let own = entry.identity == Some(self.identity.identity);
+                            html! {
+                                <tr class={if own {"won"} else {""}}>
+                                    <td>{i + 1}</td>
+                                    <td>{&entry.username}</td>
+                                    <td>{format!("{:.0}", entry.rating)}</td>
+                                    <td>{entry.games}</td>
+                                    <td>{entry.wins}</td>
+                                </tr>
+                            }
+                        })
+                    }
+                </table>
+                <button onclick=self.link.callback(|_| Msg::ToggleLeaderboard)>{self.t("close")}</button>
+            </div>
+        }
+    }
+
+    // {min:s}, or unknown without the start and end times
+    fn format_duration(&self, seconds: Option<u64>) -> String {
+        match seconds {
+            Some(seconds) => self.t_args(
+                "duration",
+                &[
+                    ("minutes", (seconds as usize / 60).into()),
+                    ("seconds", (seconds as usize % 60).into()),
+                ],
+            ),
+            None => self.t("unknown-duration"),
+        }
+    }
 
+    fn view_match_history(&self) -> Html {
+        let lifetime = self.match_history.lifetime();
+        let offset = js_sys::Date::new_0().get_timezone_offset();
+
+        html! {
+            <div class="match-history" style={format!("display: {}", if self.history_open {"flex"} else {"none"})}>
+                <h1>{self.t("history")}</h1>
+                <div class="lifetime">
+                    <ul><a>{self.t("lifetime-games")}</a><a class="highlight">{lifetime.games}</a></ul>
+                    <ul><a>{self.t("lifetime-wins")}</a><a class="highlight">{lifetime.wins}</a></ul>
+                    <ul><a>{self.t("lifetime-win-rate")}</a><a class="highlight">{format!("{:.0}%", lifetime.win_rate * 100.0)}</a></ul>
+                    <ul><a>{self.t("lifetime-average-place")}</a><a class="highlight">{lifetime.average_place.map(|p| format!("{:.1}", p)).unwrap_or_else(|| "-".to_string())}</a></ul>
+                    <ul><a>{self.t("lifetime-cards-placed")}</a><a class="highlight">{format!("{:.1}", lifetime.cards_placed_per_game)}</a></ul>
+                    <ul><a>{self.t("lifetime-cards-drawn")}</a><a class="highlight">{format!("{:.1}", lifetime.cards_drawn_per_game)}</a></ul>
+                </div>
+                {
+                    if self.match_history.records.is_empty() {
+                        html! {<h5>{self.t("history-empty")}</h5>}
+                    } else {
+                        html! {
+                            <table class="history-table">
+                                <tr>
+                                    <th>{self.t("history-date")}</th>
+                                    <th>{self.t("history-place")}</th>
+                                    <th>{self.t("history-opponents")}</th>
+                                    <th>{self.t("history-duration")}</th>
+                                    <th>{self.t("history-cards")}</th>
+                                </tr>
+                                {
+                                    for self.match_history.records.iter().map(|record| {
+                                        let place = match record.placement {
+                                            Some(place) => self.t_args("history-placement", &[("place", place.into()), ("players", record.player_count.into())]),
+                                            None => self.t("history-watched"),
+                                        };
+                                        let opponents = record.opponents.iter().map(|o| o.username.as_str()).collect::<Vec<_>>().join(", ");
+
+                                        html! {
+                                            <tr class={if record.placement == Some(1) {"won"} else {""}}>
+                                                <td>{match_history::format_date(&chat::local_time(record.finished, offset))}</td>
+                                                <td>{place}</td>
+                                                <td title=self.t_args("winner", &[("username", record.winner.as_str().into())])>{opponents}</td>
+                                                <td>{self.format_duration(record.duration)}</td>
+                                                <td>{format!("{} / {}", record.cards_placed, record.cards_drawn)}</td>
+                                            </tr>
+                                        }
+                                    })
+                                }
+                            </table>
+                        }
+                    }
+                }
+                <button hidden=self.match_history.records.is_empty() onclick=self.link.callback(|_| Msg::ClearMatchHistory)>{self.t("history-clear")}</button>
+                <button onclick=self.link.callback(|_| Msg::ToggleMatchHistory)>{self.t("close")}</button>
             </div>
+        }
+    }
+
+    fn identity_of(&self, username: &str) -> Option<Uuid> {
+        if self.username.as_deref() == Some(username) {
+            return Some(self.identity.identity);
+        }
+
+        self.connections
+            .values()
+            .find(|player| player.username == username)
+            .and_then(|player| player.identity)
+    }
+
+    // The server vouches for identities once their tokens check out
+    fn apply_identity(&mut self, id: Uuid, identity: Uuid, account: Option<String>) {
+        if id == self.self_id() {
+            if identity == self.identity.identity {
+                if self.identity.account != account {
+                    self.identity.account = account;
+                    self.identity.save(&mut self.storage);
+                    self.send_presence();
+                }
+            } else {
+                ConsoleService::error("The server didn't accept the stored identity");
+            }
+            return;
+        }
+
+        let player = match self.connections.get_mut(&id) {
+            Some(player) => player,
+            None => return,
+        };
+        player.identity = Some(identity);
+        player.account = account;
 
+        // Blocks made before the identity was known follow it from now on
+        let username = player.username.clone();
+        let mut changed = false;
+        self.settings
+            .blocked
+            .iter_mut()
+            .filter(|b| b.identity.is_none() && b.username == username)
+            .for_each(|b| {
+                b.identity = Some(identity);
+                changed = true;
+            });
+        if changed {
+            self.settings.save(&mut self.storage);
         }
     }
+
+    fn is_blocked(&self, username: &str) -> bool {
+        let identity = self.identity_of(username);
+        self.settings
+            .blocked
+            .iter()
+            .any(|b| b.matches(username, identity))
+    }
+
+    fn silenced(&self, username: &str) -> bool {
+        self.muted_players.contains(username) || self.is_blocked(username)
+    }
+
+    fn warn_rate_limited(&mut self) {
+        let wait = self.rate_limiter.wait(now());
+        let seconds = ((wait / 1000.0).ceil() as usize).max(1);
+        let warning = self.t_args("chat-too-fast", &[("seconds", seconds.into())]);
+        self.post_to(None, ServerMessage::Warning(warning), self.chat.channel);
+    }
+
+    // Players who joined after the game started watch until the next round
+    fn is_spectator(&self, id: &Uuid) -> bool {
+        self.round.active && !self.seats.contains(id)
+    }
+
+    // Spectators and players can't talk to each other during a game, so hands can't be leaked
+    fn can_write(&self, channel: Channel) -> bool {
+        let spectator = self.is_spectator(&self.self_id());
+
+        match channel {
+            Channel::Room => !spectator,
+            Channel::Spectators => spectator,
+            Channel::Whisper(id) => {
+                self.connections.contains_key(&id) && spectator == self.is_spectator(&id)
+            }
+        }
+    }
+
+    // The tab a message belongs in, None if this client shouldn't have received it
+    fn route(&self, sender: Uuid, channel: Channel) -> Option<Channel> {
+        let own = self.self_id();
+
+        match channel {
+            Channel::Room => Some(Channel::Room),
+            Channel::Spectators if self.is_spectator(&own) => Some(Channel::Spectators),
+            Channel::Spectators => None,
+            Channel::Whisper(recipient) if sender == own => Some(Channel::Whisper(recipient)),
+            Channel::Whisper(recipient) if recipient == own => Some(Channel::Whisper(sender)),
+            Channel::Whisper(_) => None,
+        }
+    }
+
+    // The spectator tab is only offered to spectators, whisper tabs stay after the player has left
+    fn chat_tabs(&self) -> Vec<Channel> {
+        let spectator = self.is_spectator(&self.self_id());
+        let mut tabs: Vec<Channel> = self
+            .chat
+            .channels()
+            .into_iter()
+            .filter(|c| c != &Channel::Spectators || spectator)
+            .collect();
+
+        if spectator && !tabs.contains(&Channel::Spectators) {
+            tabs.insert(1, Channel::Spectators);
+        }
+
+        tabs
+    }
+
+    fn channel_label(&self, channel: Channel) -> String {
+        match channel {
+            Channel::Room => self.t("channel-room"),
+            Channel::Spectators => self.t("channel-spectators"),
+            Channel::Whisper(id) => match self.connections.get(&id) {
+                Some(player) => format!("@{}", player.username),
+                None => self.t("channel-whisper-left"),
+            },
+        }
+    }
+
+    // The chat is a closed drawer on small screens
+    fn chat_visible(&self) -> bool {
+        self.chat_open
+            || yew::utils::window()
+                .inner_width()
+                .ok()
+                .and_then(|w| w.as_f64())
+                .is_none_or(|w| w > MOBILE_WIDTH)
+    }
+
+    fn view_chat_entry(&self, entry: &Entry) -> Html {
+        let (tag, id, name, body) = match &entry.message {
+            ServerMessage::Join(username) => {
+                ("chat-connected", "connection", username.clone(), html! {})
+            }
+            ServerMessage::Leave(username) => (
+                "chat-disconnected",
+                "connection",
+                username.clone(),
+                html! {},
+            ),
+            ServerMessage::Message(username, content) => (
+                "chat-message",
+                "message",
+                username.clone(),
+                html! {
+                    <h1>
+                    {
+                        for chat::segments(&moderation::filter(content, &self.settings.word_filter)).into_iter().map(|(text, mention)| {
+                            if mention {
+                                html! {<span class="mention">{text}</span>}
+                            } else {
+                                html! {text}
+                            }
+                        })
+                    }
+                    </h1>
+                },
+            ),
+            ServerMessage::Warning(body) => (
+                "chat-warning",
+                "warning",
+                self.t("chat-client"),
+                html! {<h1>{body}</h1>},
+            ),
+            ServerMessage::Error(body) => (
+                "chat-error",
+                "error",
+                self.t("chat-server"),
+                html! {<h1>{body}</h1>},
+            ),
+        };
+
+        let reactions = match entry.id {
+            Some(message) if entry.channel == Channel::Room => {
+                let id = self.self_id();
+                let react = |emoji: &str| {
+                    let emoji = emoji.to_string();
+                    self.link
+                        .callback(move |_| Msg::React(message, emoji.clone()))
+                };
+
+                html! {
+                    <div class="chat-reactions">
+                    {
+                        for entry.reactions.iter().map(|(emoji, ids)| html! {
+                            <button class={if ids.contains(&id) {"reaction own"} else {"reaction"}} onclick=react(emoji)>
+                                {format!("{} {}", emoji, ids.len())}
+                            </button>
+                        })
+                    }
+                        <div class="reaction-picker">
+                        {
+                            for chat::REACTIONS.iter().map(|emoji| html! {
+                                <button onclick=react(emoji)>{emoji}</button>
+                            })
+                        }
+                        </div>
+                    </div>
+                }
+            }
+            _ => html! {},
+        };
+
+        html! {
+            <div class={if entry.mention {"chat-object mentioned"} else {"chat-object"}}>
+                <span class="chat-time">{chat::format_time(&entry.time)}</span>
+                {
+                    match &entry.message {
+                        ServerMessage::Message(sender, _) => html! {
+                            <ProfileAvatar username=sender.clone() profile=self.profile_of(sender) size=20 />
+                        },
+                        _ => html! {},
+                    }
+                }
+                <h3 id=id>{self.t(tag)}</h3>
+                <h2>{name}</h2>
+                {body}
+                {reactions}
+            </div>
+        }
+    }
+
+    fn play(&mut self, cue: Cue) {
+        self.audio.play(cue, &self.settings);
+    }
+
+    fn record(&mut self, id: Uuid, event: GameEvent) {
+        match event {
+            GameEvent::Placed(..) => self.play(Cue::CardPlaced),
+            GameEvent::Drew(..) => self.play(Cue::CardDrawn),
+            _ => {}
+        }
+
+        self.round.tracker.record(id, &event);
+        self.round.log.push(event);
+    }
+}
+
+// Lets screen readers pick the right pronunciation
+fn set_document_language(language: Language) {
+    if let Some(root) = yew::utils::document().document_element() {
+        root.set_attribute("lang", language.code()).ok();
+    }
+}
+
+// Seconds between the start and end times the server reported
+fn game_duration(statistics: &GameStatistics) -> Option<u64> {
+    match (statistics.start_time, statistics.end_time) {
+        (Some(start), Some(end)) => end.duration_since(start).ok().map(|d| d.as_secs()),
+        _ => None,
+    }
+}
+
+// Milliseconds since the unix epoch, taken from the browser
+pub fn now() -> f64 {
+    js_sys::Date::now()
 }
 
 pub fn to_json(data: PacketType) -> String {
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+use yew::format::Json;
+use yew::services::storage::StorageService;
+
+const KEY: &str = "uno.matches";
+pub const MAX_RECORDS: usize = 200; // the oldest games are forgotten first
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Opponent {
+    pub username: String,
+    #[serde(default)]
+    pub identity: Option<Uuid>,
+}
+
+// One finished game as seen from this client
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MatchRecord {
+    pub finished: f64,         // ms since the unix epoch
+    pub duration: Option<u64>, // seconds
+    pub room: Option<String>,
+    pub placement: Option<usize>, // 1 is the winner, None when only watching
+    pub player_count: usize,
+    pub winner: String,
+    pub opponents: Vec<Opponent>,
+    pub cards_placed: usize, // own
+    pub cards_drawn: usize,  // own
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lifetime {
+    pub games: usize, // played, watched games don't count
+    pub wins: usize,
+    pub win_rate: f64,              // 0.0 - 1.0
+    pub average_place: Option<f64>, // None before the first game
+    pub cards_placed_per_game: f64,
+    pub cards_drawn_per_game: f64,
+}
+
+// Stored in local storage, newest first
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MatchHistory {
+    pub records: Vec<MatchRecord>,
+}
+
+impl MatchHistory {
+    pub fn load(storage: &Option<StorageService>) -> MatchHistory {
+        match storage {
+            Some(storage) => {
+                let Json(history) = storage.restore(KEY);
+                history.unwrap_or_default()
+            }
+            None => MatchHistory::default(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut Option<StorageService>) {
+        if let Some(storage) = storage {
+            storage.store(KEY, Json(self));
+        }
+    }
+
+    pub fn push(&mut self, record: MatchRecord) {
+        self.records.insert(0, record);
+        self.records.truncate(MAX_RECORDS);
+    }
+
+    pub fn lifetime(&self) -> Lifetime {
+        let played: Vec<&MatchRecord> = self
+            .records
+            .iter()
+            .filter(|r| r.placement.is_some())
+            .collect();
+
+        let games = played.len();
+        let per_game = |total: usize| {
+            if games == 0 {
+                0.0
+            } else {
+                total as f64 / games as f64
+            }
+        };
+
+        let wins = played.iter().filter(|r| r.placement == Some(1)).count();
+        let places: usize = played.iter().filter_map(|r| r.placement).sum();
+
+        Lifetime {
+            games,
+            wins,
+            win_rate: per_game(wins),
+            average_place: if games == 0 {
+                None
+            } else {
+                Some(per_game(places))
+            },
+            cards_placed_per_game: per_game(played.iter().map(|r| r.cards_placed).sum()),
+            cards_drawn_per_game: per_game(played.iter().map(|r| r.cards_drawn).sum()),
+        }
+    }
+}
+
+// The winner isn't included in the placements, which list everyone else from second place down.
+// Seated players missing from them finished last.
+pub fn placement(
+    username: &str,
+    winner: bool,
+    placements: &[String],
+    seated: bool,
+    player_count: usize,
+) -> Option<usize> {
+    if winner {
+        return Some(1);
+    }
+
+    match placements.iter().position(|u| u == username) {
+        Some(index) => Some(index + 2),
+        None if seated => Some(player_count.max(placements.len() + 2)),
+        None => None,
+    }
+}
+
+pub fn format_date(time: &OffsetDateTime) -> String {
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}",
+        time.year(),
+        time.month() as u8,
+        time.day(),
+        time.hour(),
+        time.minute()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(placement: Option<usize>, cards_placed: usize) -> MatchRecord {
+        MatchRecord {
+            finished: 0.0,
+            duration: Some(300),
+            room: None,
+            placement,
+            player_count: 4,
+            winner: "ada".to_string(),
+            opponents: Vec::new(),
+            cards_placed,
+            cards_drawn: 2,
+        }
+    }
+
+    #[test]
+    fn aggregates_played_games() {
+        let mut history = MatchHistory::default();
+        history.push(record(Some(1), 10));
+        history.push(record(Some(3), 20));
+        history.push(record(None, 99));
+
+        let lifetime = history.lifetime();
+        assert_eq!(lifetime.games, 2);
+        assert_eq!(lifetime.wins, 1);
+        assert_eq!(lifetime.win_rate, 0.5);
+        assert_eq!(lifetime.average_place, Some(2.0));
+        assert_eq!(lifetime.cards_placed_per_game, 15.0);
+        assert_eq!(MatchHistory::default().lifetime().average_place, None);
+    }
+
+    #[test]
+    fn finds_placements() {
+        let placements = vec!["bob".to_string(), "eve".to_string()];
+
+        assert_eq!(placement("ada", true, &placements, true, 4), Some(1));
+        assert_eq!(placement("eve", false, &placements, true, 4), Some(3));
+        assert_eq!(placement("max", false, &placements, true, 4), Some(4));
+        assert_eq!(placement("max", false, &placements, false, 4), None);
+    }
+
+    #[test]
+    fn keeps_the_newest_records() {
+        let mut history = MatchHistory::default();
+        (0..MAX_RECORDS + 5).for_each(|i| history.push(record(Some(1), i)));
+
+        assert_eq!(history.records.len(), MAX_RECORDS);
+        assert_eq!(history.records[0].cards_placed, MAX_RECORDS + 4);
+    }
+}
@@ -0,0 +1,73 @@
+// Quick Play keeps its own connection to the server's queue until a room is found,
+// then joins the room like any other.
+pub const QUEUE_PATH: &str = "queue";
+pub const TICK: u64 = 1000; // ms between updates of the waiting time
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueState {
+    pub joined: f64,              // ms
+    pub position: Option<usize>,  // 1 is next to be matched
+    estimate: Option<(u64, f64)>, // seconds the server estimated, when it did (ms)
+}
+
+impl QueueState {
+    pub fn new(now: f64) -> QueueState {
+        QueueState {
+            joined: now,
+            position: None,
+            estimate: None,
+        }
+    }
+
+    pub fn update(&mut self, position: usize, estimate: Option<u64>, now: f64) {
+        self.position = Some(position);
+        self.estimate = estimate.map(|seconds| (seconds, now));
+    }
+
+    pub fn waited(&self, now: f64) -> u64 {
+        ((now - self.joined).max(0.0) / 1000.0) as u64
+    }
+
+    // Counts the server's estimate down between updates, it never goes below a second
+    // as the match is only certain once it has been found
+    pub fn remaining(&self, now: f64) -> Option<u64> {
+        self.estimate.map(|(seconds, at)| {
+            let passed = ((now - at).max(0.0) / 1000.0) as u64;
+            seconds.saturating_sub(passed).max(1)
+        })
+    }
+}
+
+// Rooms are joined by their path on the server
+pub fn valid_room(room: &str) -> bool {
+    !room.trim().is_empty()
+        && room
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_the_estimate_down() {
+        let mut queue = QueueState::new(0.0);
+        assert_eq!(queue.remaining(5000.0), None);
+
+        queue.update(3, Some(30), 2000.0);
+        assert_eq!(queue.waited(12_500.0), 12);
+        assert_eq!(queue.remaining(12_500.0), Some(20));
+        assert_eq!(queue.remaining(60_000.0), Some(1));
+
+        queue.update(1, None, 13_000.0);
+        assert_eq!((queue.position, queue.remaining(13_000.0)), (Some(1), None));
+    }
+
+    #[test]
+    fn validates_rooms() {
+        assert!(valid_room("c05554ae-b4ee-4976-ac05-97aaf3c98a24"));
+        assert!(!valid_room(""));
+        assert!(!valid_room("../admin"));
+    }
+}
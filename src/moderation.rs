@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+pub const CHAT_LIMIT: (usize, f64) = (5, 5000.0); // messages allowed inside the window, ms
+const MASK: char = '*';
+
+// Blocks follow the identity once it's known, so a new username doesn't get around them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "StoredBlock")]
+pub struct Block {
+    pub username: String, // as it was when blocked
+    pub identity: Option<Uuid>,
+}
+
+// Blocks used to be stored as plain usernames
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredBlock {
+    Username(String),
+    Block {
+        username: String,
+        identity: Option<Uuid>,
+    },
+}
+
+impl From<StoredBlock> for Block {
+    fn from(stored: StoredBlock) -> Self {
+        match stored {
+            StoredBlock::Username(username) => Block {
+                username,
+                identity: None,
+            },
+            StoredBlock::Block { username, identity } => Block { username, identity },
+        }
+    }
+}
+
+impl Block {
+    pub fn matches(&self, username: &str, identity: Option<Uuid>) -> bool {
+        match (self.identity, identity) {
+            (Some(blocked), Some(identity)) => blocked == identity,
+            _ => self.username == username,
+        }
+    }
+}
+
+// Replaces filtered words with asterisks, ignoring case.
+// Only whole words are matched, so filtering "ass" leaves "class" alone.
+pub fn filter(content: &str, words: &[String]) -> String {
+    let words: Vec<String> = words
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        return content.to_string();
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, output: &mut String| {
+        if words.contains(&word.to_lowercase()) {
+            output.extend(word.chars().map(|_| MASK));
+        } else {
+            output.push_str(word);
+        }
+        word.clear();
+    };
+
+    for c in content.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut output);
+            output.push(c);
+        }
+    }
+    flush(&mut word, &mut output);
+
+    output
+}
+
+// The filter is edited as a single comma separated field in the settings
+pub fn parse_words(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// Sliding window over the times of the latest messages sent
+pub struct RateLimiter {
+    limit: usize,
+    window: f64,         // ms
+    sent: VecDeque<f64>, // ms
+}
+
+impl RateLimiter {
+    pub fn new((limit, window): (usize, f64)) -> RateLimiter {
+        RateLimiter {
+            limit,
+            window,
+            sent: VecDeque::new(),
+        }
+    }
+
+    fn expire(&mut self, now: f64) {
+        while self.sent.front().is_some_and(|t| now - t >= self.window) {
+            self.sent.pop_front();
+        }
+    }
+
+    // Records a message sent now, or refuses it when too many were sent inside the window
+    pub fn try_send(&mut self, now: f64) -> Result<(), ()> {
+        self.expire(now);
+        if self.sent.len() >= self.limit {
+            return Err(());
+        }
+
+        self.sent.push_back(now);
+        Ok(())
+    }
+
+    // ms left until the next message is allowed
+    pub fn wait(&mut self, now: f64) -> f64 {
+        self.expire(now);
+        match self.sent.front() {
+            Some(t) if self.sent.len() >= self.limit => t + self.window - now,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_whole_words() {
+        let words = parse_words("darn, Heck ,");
+        assert_eq!(words, vec!["darn".to_string(), "Heck".to_string()]);
+
+        assert_eq!(filter("Darn it, heck!", &words), "**** it, ****!");
+        assert_eq!(filter("darned checkers", &words), "darned checkers");
+        assert_eq!(filter("darn", &[]), "darn");
+    }
+
+    #[test]
+    fn blocks_follow_identity() {
+        let identity = Uuid::new_v4();
+        let blocks: Vec<Block> = serde_json::from_str(&format!(
+            r#"["bob", {{"username": "eve", "identity": "{}"}}]"#,
+            identity
+        ))
+        .unwrap();
+
+        assert!(blocks[0].matches("bob", None));
+        assert!(blocks[0].matches("bob", Some(Uuid::new_v4())));
+        assert!(blocks[1].matches("eve2", Some(identity)));
+        assert!(!blocks[1].matches("eve", Some(Uuid::new_v4())));
+    }
+
+    #[test]
+    fn limits_message_rate() {
+        let (limit, window) = CHAT_LIMIT;
+        let mut limiter = RateLimiter::new(CHAT_LIMIT);
+        (0..limit).for_each(|i| assert!(limiter.try_send(i as f64 * 100.0).is_ok()));
+
+        assert!(limiter.try_send(1000.0).is_err());
+        assert_eq!(limiter.wait(1000.0), window - 1000.0);
+        assert!(limiter.try_send(window).is_ok());
+    }
+}
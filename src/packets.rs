@@ -1,4 +1,10 @@
-use crate::game::{Card, GameStatistics};
+use crate::chat::Channel;
+use crate::emote::Emote;
+use crate::friends::Presence;
+use crate::game::{Card, Direction, GameOptions, GameStatistics, StateSnapshot};
+use crate::identity::Identity;
+use crate::profile::Profile;
+use crate::rating::LeaderboardEntry;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use uuid::Uuid;
@@ -6,20 +12,123 @@ use uuid::Uuid;
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type", content = "data")]
 pub enum PacketType {
-    Register(String),                                          // username
-    GameData(Uuid, String, Vec<(Uuid, String)>), // self_id, self_username, Vec<(id, username)>
-    Connect(Uuid, String),                       // id, username
-    Disconnect(Uuid, String),                    // id, username
-    Message(String, String),                     // sender, content
-    StartGame(String),                           // option
-    StatusUpdatePublic(Uuid, String, usize, Card), // id, username, card-count, current
-    StatusUpdatePrivate(Vec<Card>, Card),        // cards, current
-    AllowedCardsUpdate(Vec<Card>),               // allowed-cards
-    DrawCard(u8),                                // amount
-    PlaceCard(usize),                            // index
-    EndTurn,                                     //
-    ColorSwitch(String),                         // color
-    TurnUpdate(Uuid, Uuid),                      // current, next
+    Register(String, Profile, Identity), // username, profile, identity
+    GameData(Uuid, String, Vec<(Uuid, String, Profile)>), // self_id, self_username, Vec<(id, username, profile)>
+    Connect(Uuid, String, Profile),                       // id, username, profile
+    Disconnect(Uuid, String),                             // id, username
+    Message(String, String),                              // sender, content
+    StartGame(String),                                    // options as json
+    StatusUpdatePublic(Uuid, String, usize, Card),        // id, username, card-count, current
+    StatusUpdatePrivate(Vec<Card>, Card),                 // cards, current
+    AllowedCardsUpdate(Vec<Card>),                        // allowed-cards
+    DrawCard(u8),                                         // amount
+    PlaceCard(usize),                                     // index
+    EndTurn,                                              //
+    ColorSwitch(String),                                  // color
+    TurnUpdate(Uuid, Uuid),                               // current, next
     WinUpdate(Uuid, String, VecDeque<String>, GameStatistics), // id, username, placements, statistics
     Error(u64, String),                                        // error-code, body
+    RematchVote(bool),                                         // ready
+    RematchVoteUpdate(Uuid, bool),                             // voter id, ready
+    OptionsUpdate(GameOptions),                                // options
+    Resync,                                                    // request the full state
+    StateSnapshot(StateSnapshot),                              // full state
+    SeatOrder(Vec<Uuid>),                                      // ids in play order
+    DirectionUpdate(Direction),                                // direction
+    Reaction(Uuid, String, bool),                              // message id, emoji, added
+    ReactionUpdate(Uuid, Uuid, String, bool),                  // id, message id, emoji, added
+    ChannelMessage(Channel, String),                           // channel, content
+    ChannelMessageUpdate(Uuid, Uuid, Channel, String),         // id, sender id, channel, content
+    Emote(Emote),                                              // emote
+    EmoteUpdate(Uuid, Emote),                                  // id, emote
+    IdentityUpdate(Uuid, Uuid, Option<String>),                // id, identity, account
+    ClaimAccount(String),                                      // account name
+    FetchLeaderboard(usize),                                   // limit
+    LeaderboardUpdate(Vec<LeaderboardEntry>),                  // entries, best first
+    QueueJoin(String, Identity, f64),                          // username, identity, rating hint
+    QueueLeave,                                                //
+    QueueStatus(usize, Option<u64>), // position, estimated wait in seconds
+    MatchFound(String),              // room id
+    PresenceHello(Identity, Option<String>, Vec<Uuid>), // identity, name friends see, friends' identities
+    PresenceFollow(Vec<Uuid>),                          // friends' identities
+    PresenceUpdate(Uuid, Presence),                     // identity, presence
+    Invite(Uuid, String),                               // friend's identity, room id
+    InviteReceived(Uuid, String, String),               // identity, username, room id
+}
+
+// The client keeps a connection each for the room, matchmaking and presence,
+// and only accepts the packets meant for the connection they came in on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Socket {
+    Room,
+    Queue,
+    Presence,
+}
+
+impl PacketType {
+    pub fn socket(&self) -> Socket {
+        match self {
+            PacketType::QueueJoin(..)
+            | PacketType::QueueLeave
+            | PacketType::QueueStatus(..)
+            | PacketType::MatchFound(_) => Socket::Queue,
+            PacketType::PresenceHello(..)
+            | PacketType::PresenceFollow(_)
+            | PacketType::PresenceUpdate(..)
+            | PacketType::Invite(..)
+            | PacketType::InviteReceived(..) => Socket::Presence,
+            _ => Socket::Room,
+        }
+    }
+}
+
+// Packets meant for another connection are ignored rather than rejected
+pub fn receive(socket: Socket, json: &str) -> Result<Option<PacketType>, serde_json::Error> {
+    let packet: PacketType = serde_json::from_str(json)?;
+
+    Ok(Some(packet).filter(|packet| packet.socket() == socket))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_only_accepts_matchmaking() {
+        let status = r#"{"type":"QueueStatus","data":[3,null]}"#;
+        let found = r#"{"type":"MatchFound","data":"room"}"#;
+        let message = r#"{"type":"Message","data":["bob","hi"]}"#;
+
+        assert!(matches!(
+            receive(Socket::Queue, status),
+            Ok(Some(PacketType::QueueStatus(3, None)))
+        ));
+        assert!(matches!(
+            receive(Socket::Queue, found),
+            Ok(Some(PacketType::MatchFound(_)))
+        ));
+        assert!(matches!(receive(Socket::Queue, message), Ok(None)));
+        assert!(matches!(receive(Socket::Room, status), Ok(None)));
+        assert!(receive(Socket::Queue, "{").is_err());
+    }
+
+    #[test]
+    fn presence_ignores_room_packets() {
+        let id = Uuid::new_v4();
+        let invite = format!(
+            r#"{{"type":"InviteReceived","data":["{}","bob","room"]}}"#,
+            id
+        );
+        let disconnect = format!(r#"{{"type":"Disconnect","data":["{}","bob"]}}"#, id);
+
+        assert!(matches!(
+            receive(Socket::Presence, &invite),
+            Ok(Some(PacketType::InviteReceived(..)))
+        ));
+        assert!(matches!(receive(Socket::Presence, &disconnect), Ok(None)));
+        assert!(matches!(
+            receive(Socket::Room, &disconnect),
+            Ok(Some(PacketType::Disconnect(..)))
+        ));
+    }
 }
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+// Color palettes for the highlights and color glyphs, the defaults match index.css
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Palette {
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Palette::Default,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Palette::Default => "Default",
+            Palette::Deuteranopia => "Deuteranopia",
+            Palette::Protanopia => "Protanopia",
+            Palette::Tritanopia => "Tritanopia",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Palette> {
+        Palette::ALL.iter().find(|p| p.label() == label).copied()
+    }
+
+    // red, yellow, green, blue
+    pub fn colors(&self) -> [&'static str; 4] {
+        match self {
+            Palette::Default => ["#FF5555", "#FFAA00", "#55AA55", "#5555FF"],
+            Palette::Deuteranopia => ["#D55E00", "#F0E442", "#009E73", "#0072B2"],
+            Palette::Protanopia => ["#DC3220", "#FFC20A", "#40B0A6", "#005AB5"],
+            Palette::Tritanopia => ["#D81B60", "#FE6100", "#1E88E5", "#004D40"],
+        }
+    }
+
+    // Overrides the css color variables for everything inside the element
+    pub fn css_variables(&self) -> String {
+        let [red, yellow, green, blue] = self.colors();
+
+        format!(
+            "--red: {}; --yellow: {}; --green: {}; --blue: {}; --disabled: {};",
+            red, yellow, green, blue, red
+        )
+    }
+}
+
+// Shape drawn on top of a card, so colors can be told apart without seeing them
+pub fn glyph(color: &str) -> &'static str {
+    match color {
+        "Red" => "◆",
+        "Yellow" => "●",
+        "Green" => "▲",
+        "Blue" => "■",
+        _ => "",
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+pub const AVATARS: [&str; 12] = [
+    "🦊", "🐼", "🐸", "🐙", "🦉", "🐯", "🐧", "🐢", "🦄", "🐝", "🐳", "🦖",
+];
+pub const MAX_BIO: usize = 80; // chars
+
+// Generated avatars pick their background from the username, so they stay the same between sessions
+const GENERATED_COLORS: [&str; 8] = [
+    "#E57373", "#F06292", "#BA68C8", "#7986CB", "#4FC3F7", "#4DB6AC", "#AED581", "#FFB74D",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Avatar {
+    #[default]
+    Initials,
+    BuiltIn(usize), // index into AVATARS
+}
+
+// Chosen by the player, sent to everyone in the room at Register and Connect
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub avatar: Avatar,
+    pub color: Option<String>, // "#rrggbb", generated from the username when missing
+    pub bio: String,
+}
+
+impl Profile {
+    // Anything that can't be drawn falls back to the defaults instead of rejecting the player
+    pub fn sanitized(mut self) -> Profile {
+        if let Avatar::BuiltIn(index) = self.avatar {
+            if index >= AVATARS.len() {
+                self.avatar = Avatar::Initials;
+            }
+        }
+
+        if !self.color.as_deref().is_some_and(is_hex_color) {
+            self.color = None;
+        }
+
+        self.bio = self.bio.trim().chars().take(MAX_BIO).collect();
+        self
+    }
+
+    pub fn color(&self, username: &str) -> String {
+        match &self.color {
+            Some(color) => color.clone(),
+            None => {
+                let hash = username.bytes().fold(0usize, |hash, b| {
+                    hash.wrapping_mul(31).wrapping_add(b as usize)
+                });
+                GENERATED_COLORS[hash % GENERATED_COLORS.len()].to_string()
+            }
+        }
+    }
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// "ada lovelace" -> "AL", "bob" -> "B"
+pub fn initials(username: &str) -> String {
+    let initials: String = username
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-' || c == '.')
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ProfileAvatarProps {
+    pub username: String,
+    pub profile: Profile,
+    #[prop_or_default]
+    pub size: Option<u32>, // px, the stylesheet decides when missing
+}
+
+pub struct ProfileAvatar {
+    props: ProfileAvatarProps,
+}
+
+impl Component for ProfileAvatar {
+    type Message = ();
+    type Properties = ProfileAvatarProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            self.props = props;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn view(&self) -> Html {
+        let ProfileAvatarProps {
+            username,
+            profile,
+            size,
+        } = &self.props;
+
+        let content = match profile.avatar {
+            Avatar::BuiltIn(index) => AVATARS.get(index).copied().unwrap_or("?").to_string(),
+            Avatar::Initials => initials(username),
+        };
+
+        let mut style = format!("background-color: {};", profile.color(username));
+        if let Some(size) = size {
+            style.push_str(&format!(
+                " width: {0}px; height: {0}px; font-size: {1}px;",
+                size,
+                size / 2
+            ));
+        }
+
+        html! {
+            <div class="avatar" style=style title={if profile.bio.is_empty() {username.clone()} else {format!("{}: {}", username, profile.bio)}} aria-hidden="true">
+                {content}
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_initials() {
+        assert_eq!(initials("ada lovelace"), "AL");
+        assert_eq!(initials("bob"), "B");
+        assert_eq!(initials("élan_vital_x"), "ÉV");
+        assert_eq!(initials(""), "?");
+    }
+
+    #[test]
+    fn sanitizes_profiles() {
+        let profile = Profile {
+            avatar: Avatar::BuiltIn(AVATARS.len()),
+            color: Some("red; background: url(x)".to_string()),
+            bio: "x".repeat(MAX_BIO + 10),
+        }
+        .sanitized();
+
+        assert_eq!(profile.avatar, Avatar::Initials);
+        assert_eq!(profile.color, None);
+        assert_eq!(profile.bio.chars().count(), MAX_BIO);
+        assert_eq!(profile.color("bob"), profile.color("bob"));
+
+        let colored = Profile {
+            color: Some("#12abEF".to_string()),
+            ..Profile::default()
+        };
+        assert_eq!(colored.clone().sanitized(), colored);
+    }
+}
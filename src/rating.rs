@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use yew::format::Json;
+use yew::services::storage::StorageService;
+
+const KEY: &str = "uno.ratings";
+pub const INITIAL: f64 = 1500.0;
+pub const LEADERBOARD_SIZE: usize = 50;
+const K: f64 = 32.0; // most a rating can move in one game
+const PROVISIONAL_K: f64 = 64.0; // new players find their level faster
+const PROVISIONAL_GAMES: usize = 10;
+const SCALE: f64 = 400.0; // a difference this large makes the higher rated player 10 times as likely to win
+
+// Chance of a player rated `a` finishing above one rated `b`
+pub fn expected(a: f64, b: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((b - a) / SCALE))
+}
+
+// Multiplayer Elo: a game is scored as every pair of players playing each other,
+// the higher placed one winning. Ratings are in finishing order and paired with the games played so far.
+// The change is averaged over the opponents, so bigger games don't move ratings more.
+pub fn update(ratings: &[(f64, usize)]) -> Vec<f64> {
+    let opponents = ratings.len().saturating_sub(1);
+    if opponents == 0 {
+        return ratings.iter().map(|(rating, _)| *rating).collect();
+    }
+
+    ratings
+        .iter()
+        .enumerate()
+        .map(|(i, (rating, games))| {
+            let score: f64 = ratings
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, (other, _))| {
+                    let actual = if i < j { 1.0 } else { 0.0 };
+                    actual - expected(*rating, *other)
+                })
+                .sum();
+
+            let k = if *games < PROVISIONAL_GAMES {
+                PROVISIONAL_K
+            } else {
+                K
+            };
+            rating + k * score / opponents as f64
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaderboardSource {
+    Server,
+    Local, // offline, or the server doesn't keep ratings
+}
+
+// One row of a leaderboard, sent by the server or kept locally
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub username: String,
+    #[serde(default)]
+    pub identity: Option<Uuid>,
+    pub rating: f64,
+    pub games: usize,
+    pub wins: usize,
+}
+
+impl LeaderboardEntry {
+    fn new(username: &str, identity: Option<Uuid>) -> LeaderboardEntry {
+        LeaderboardEntry {
+            username: username.to_string(),
+            identity,
+            rating: INITIAL,
+            games: 0,
+            wins: 0,
+        }
+    }
+}
+
+// Ratings from the games played on this device, used when the server doesn't keep any.
+// Players are kept by identity when it's known and by username otherwise.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct LocalRatings {
+    pub players: HashMap<String, LeaderboardEntry>,
+}
+
+fn key(username: &str, identity: Option<Uuid>) -> String {
+    match identity {
+        Some(identity) => identity.to_string(),
+        None => format!("name:{}", username),
+    }
+}
+
+impl LocalRatings {
+    pub fn load(storage: &Option<StorageService>) -> LocalRatings {
+        match storage {
+            Some(storage) => {
+                let Json(ratings) = storage.restore(KEY);
+                ratings.unwrap_or_default()
+            }
+            None => LocalRatings::default(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut Option<StorageService>) {
+        if let Some(storage) = storage {
+            storage.store(KEY, Json(self));
+        }
+    }
+
+    pub fn rating(&self, username: &str, identity: Option<Uuid>) -> f64 {
+        self.players
+            .get(&key(username, identity))
+            .map(|entry| entry.rating)
+            .unwrap_or(INITIAL)
+    }
+
+    // Players as (username, identity) in finishing order, the winner first
+    pub fn record(&mut self, players: &[(String, Option<Uuid>)]) {
+        let entries: Vec<LeaderboardEntry> = players
+            .iter()
+            .map(|(username, identity)| {
+                let mut entry = self
+                    .players
+                    .get(&key(username, *identity))
+                    .cloned()
+                    .unwrap_or_else(|| LeaderboardEntry::new(username, *identity));
+                entry.username = username.clone();
+                entry
+            })
+            .collect();
+
+        let ratings: Vec<(f64, usize)> = entries.iter().map(|e| (e.rating, e.games)).collect();
+
+        entries
+            .into_iter()
+            .zip(update(&ratings))
+            .enumerate()
+            .for_each(|(place, (mut entry, rating))| {
+                entry.rating = rating;
+                entry.games += 1;
+                if place == 0 {
+                    entry.wins += 1;
+                }
+                self.players
+                    .insert(key(&entry.username, entry.identity), entry);
+            });
+    }
+
+    pub fn leaderboard(&self) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> = self.players.values().cloned().collect();
+        entries.sort_by(|a, b| {
+            b.rating
+                .partial_cmp(&a.rating)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.username.cmp(&b.username))
+        });
+        entries.truncate(LEADERBOARD_SIZE);
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn expected_scores_are_symmetric() {
+        assert!(close(expected(1500.0, 1500.0), 0.5));
+        assert!(close(expected(1900.0, 1500.0), 10.0 / 11.0));
+        assert!(close(
+            expected(1620.0, 1480.0) + expected(1480.0, 1620.0),
+            1.0
+        ));
+    }
+
+    #[test]
+    fn two_player_games_match_elo() {
+        let updated = update(&[(1500.0, 20), (1500.0, 20)]);
+        assert!(close(updated[0], 1516.0));
+        assert!(close(updated[1], 1484.0));
+
+        // An upset moves the ratings more than an expected result
+        let upset = update(&[(1400.0, 20), (1600.0, 20)]);
+        let expected_result = update(&[(1600.0, 20), (1400.0, 20)]);
+        assert!(upset[0] - 1400.0 > expected_result[0] - 1600.0);
+    }
+
+    #[test]
+    fn multiplayer_updates_are_zero_sum_and_ordered() {
+        let ratings = [(1500.0, 20), (1550.0, 20), (1450.0, 20), (1500.0, 20)];
+        let updated = update(&ratings);
+
+        let before: f64 = ratings.iter().map(|(r, _)| r).sum();
+        assert!(close(updated.iter().sum::<f64>(), before));
+
+        // Equal ratings end up in finishing order
+        assert!(updated[0] - 1500.0 > 0.0);
+        assert!(updated[3] - 1500.0 < 0.0);
+        assert!(updated[0] > updated[3]);
+    }
+
+    #[test]
+    fn provisional_ratings_move_faster() {
+        let new = update(&[(1500.0, 0), (1500.0, 20)]);
+        assert!(close(new[0], 1532.0));
+        assert!(close(new[1], 1484.0));
+        assert_eq!(update(&[(1500.0, 3)]), vec![1500.0]);
+    }
+
+    #[test]
+    fn local_ratings_follow_identity() {
+        let identity = Some(Uuid::new_v4());
+        let mut ratings = LocalRatings::default();
+
+        ratings.record(&[("ada".to_string(), identity), ("bob".to_string(), None)]);
+        ratings.record(&[("ada2".to_string(), identity), ("bob".to_string(), None)]);
+
+        let leaderboard = ratings.leaderboard();
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].username, "ada2");
+        assert_eq!((leaderboard[0].games, leaderboard[0].wins), (2, 2));
+        assert!(ratings.rating("bob", None) < INITIAL);
+    }
+}
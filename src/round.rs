@@ -0,0 +1,141 @@
+use crate::game::{Card, Direction, EndStatus, RematchVotes, StateSnapshot};
+use crate::history::GameLog;
+use crate::statistics::StatisticsTracker;
+use uuid::Uuid;
+
+// Everything that only lasts for one round, the hand is kept by the component
+// since it also moves the keyboard focus
+pub struct Round {
+    pub number: usize,
+    pub active: bool,
+    pub ended: bool,
+    pub turn: bool,
+    pub next: bool,
+    pub selecting: bool, // choosing the color of a wild card
+    pub current: Option<Card>,
+    pub color: Option<String>,
+    pub direction: Direction,
+    pub log: GameLog,
+    pub tracker: StatisticsTracker,
+    pub end_status: Option<EndStatus>,
+    pub rematch_votes: RematchVotes,
+}
+
+impl Round {
+    pub fn new() -> Round {
+        Round {
+            number: 1,
+            active: false,
+            ended: false,
+            turn: false,
+            next: false,
+            selecting: false,
+            current: None,
+            color: None,
+            direction: Direction::Clockwise,
+            log: GameLog::new(),
+            tracker: StatisticsTracker::new(),
+            end_status: None,
+            rematch_votes: RematchVotes::default(),
+        }
+    }
+
+    // An ended round counts even if the next one is started by a snapshot
+    pub fn reset(&mut self) {
+        let number = self.number + usize::from(self.end_status.is_some());
+
+        *self = Round {
+            number,
+            ..Round::new()
+        };
+    }
+
+    // Nothing from before the snapshot is kept, whatever it doesn't carry starts over
+    pub fn apply_snapshot(&mut self, id: Uuid, snapshot: &StateSnapshot) {
+        self.reset();
+
+        self.active = snapshot.active;
+        self.turn = snapshot.turn == Some(id);
+        self.next = snapshot.next == Some(id);
+        self.current = snapshot.current.clone();
+        self.color = snapshot.color.clone();
+        self.direction = snapshot.direction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{GameOptions, GameStatistics, Seat};
+    use crate::history::GameEvent;
+    use std::collections::VecDeque;
+
+    fn snapshot(id: Uuid, other: Uuid) -> StateSnapshot {
+        StateSnapshot {
+            active: true,
+            seats: vec![
+                Seat {
+                    id,
+                    username: String::from("alice"),
+                    card_count: 7,
+                },
+                Seat {
+                    id: other,
+                    username: String::from("bob"),
+                    card_count: 7,
+                },
+            ],
+            spectators: Vec::new(),
+            turn: Some(other),
+            next: Some(id),
+            current: None,
+            color: Some(String::from("red")),
+            direction: Direction::CounterClockwise,
+            cards: Vec::new(),
+            allowed_cards: Vec::new(),
+            options: GameOptions::new(),
+        }
+    }
+
+    #[test]
+    fn snapshot_replaces_an_ended_round() {
+        let (id, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut round = Round::new();
+        round.ended = true;
+        round.end_status = Some(EndStatus {
+            winner_id: other,
+            winner: String::from("bob"),
+            placements: VecDeque::new(),
+            statistics: GameStatistics::default(),
+        });
+        round.log.push(GameEvent::Turn(String::from("bob")));
+        round.rematch_votes.vote(other, true);
+
+        round.apply_snapshot(id, &snapshot(id, other));
+
+        assert_eq!(round.number, 2);
+        assert!(round.active);
+        assert!(!round.ended);
+        assert!(round.end_status.is_none());
+        assert!(round.log.events.is_empty());
+        assert!(!round.rematch_votes.contains(&other));
+        assert!(!round.turn && round.next);
+        assert_eq!(round.color.as_deref(), Some("red"));
+        assert_eq!(round.direction, Direction::CounterClockwise);
+    }
+
+    #[test]
+    fn snapshot_stops_color_selection() {
+        let (id, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut round = Round::new();
+        round.active = true;
+        round.turn = true;
+        round.selecting = true;
+
+        round.apply_snapshot(id, &snapshot(id, other));
+
+        assert_eq!(round.number, 1);
+        assert!(!round.selecting);
+        assert!(!round.turn);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::game::Score;
+use crate::i18n::{self, Language};
+use uuid::Uuid;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ScoreboardProps {
+    pub score: Score,
+    pub players: Vec<(Uuid, String)>, // id, username
+    #[prop_or_default]
+    pub language: Language,
+}
+
+pub struct Scoreboard {
+    props: ScoreboardProps,
+}
+
+// Players ordered by points, with their rank. Players sharing points share the rank.
+pub fn standings(score: &Score, players: &[(Uuid, String)]) -> Vec<(usize, String, usize)> {
+    let mut rows: Vec<(String, usize)> = players
+        .iter()
+        .map(|(id, username)| (username.clone(), score.get(id)))
+        .collect();
+
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut standings: Vec<(usize, String, usize)> = Vec::new();
+    rows.into_iter()
+        .enumerate()
+        .for_each(|(i, (username, points))| {
+            let rank = match standings.last() {
+                Some((rank, _, previous)) if *previous == points => *rank,
+                _ => i + 1,
+            };
+            standings.push((rank, username, points));
+        });
+
+    standings
+}
+
+impl Component for Scoreboard {
+    type Message = ();
+    type Properties = ScoreboardProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            self.props = props;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn view(&self) -> Html {
+        let score = &self.props.score;
+        let language = self.props.language;
+
+        html! {
+            <div class="scoreboard">
+                <h2>{i18n::format(language, "scoreboard-title", &[("target", score.target.into())])}</h2>
+                <h5>{i18n::format(language, "rounds-played", &[("n", score.rounds.into())])}</h5>
+                {
+                    for standings(score, &self.props.players).into_iter().map(|(rank, username, points)| {
+                        html! {
+                            <ul>
+                                <a>{format!("{}. {}", rank, username)}</a>
+                                <a class="highlight">{format!(" {}", points)}</a>
+                            </ul>
+                        }
+                    })
+                }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Card;
+
+    #[test]
+    fn standings_are_ordered_by_points() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut score = Score::new(Score::DEFAULT_TARGET);
+
+        score.award(b, &[(a, vec![Card::new("Switch", "Red", a)])]);
+        score.award(c, &[(a, vec![Card::new("Seven", "Red", a)])]);
+
+        let players = vec![
+            (a, "alice".to_string()),
+            (b, "bob".to_string()),
+            (c, "carol".to_string()),
+        ];
+
+        assert_eq!(
+            standings(&score, &players),
+            vec![
+                (1, "bob".to_string(), 50),
+                (2, "carol".to_string(), 7),
+                (3, "alice".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn tied_players_share_rank() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut score = Score::new(Score::DEFAULT_TARGET);
+
+        score.award(a, &[(c, vec![Card::new("Nine", "Blue", c)])]);
+        score.award(b, &[(c, vec![Card::new("Nine", "Green", c)])]);
+
+        let players = vec![
+            (c, "carol".to_string()),
+            (b, "bob".to_string()),
+            (a, "alice".to_string()),
+        ];
+
+        assert_eq!(
+            standings(&score, &players),
+            vec![
+                (1, "alice".to_string(), 9),
+                (1, "bob".to_string(), 9),
+                (3, "carol".to_string(), 0),
+            ]
+        );
+    }
+}
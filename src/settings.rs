@@ -0,0 +1,72 @@
+use crate::i18n::Language;
+use crate::moderation::Block;
+use crate::palette::Palette;
+use crate::profile::Profile;
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+use yew::format::Json;
+use yew::services::storage::StorageService;
+
+const KEY: &str = "uno.settings";
+
+// Stored in local storage, fields missing from older versions fall back to their defaults
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub volume: f64, // 0.0 - 1.0
+    pub muted: bool,
+    pub palette: Palette,
+    pub color_glyphs: bool,
+    pub theme: String,             // name
+    pub custom_themes: Vec<Theme>, // loaded by the player
+    pub language: Language,
+    pub word_filter: Vec<String>, // masked in chat messages
+    pub blocked: Vec<Block>,      // players whose messages are never shown
+    pub emotes: bool,             // speech bubbles from quick emotes
+    pub profile: Profile,         // sent when registering
+}
+
+impl Settings {
+    pub fn new() -> Settings {
+        Settings {
+            volume: 0.5,
+            muted: false,
+            palette: Palette::Default,
+            color_glyphs: false,
+            theme: Theme::default().name,
+            custom_themes: Vec::new(),
+            language: Language::English,
+            word_filter: Vec::new(),
+            blocked: Vec::new(),
+            emotes: true,
+            profile: Profile::default(),
+        }
+    }
+
+    pub fn load(storage: &Option<StorageService>) -> Settings {
+        match storage {
+            Some(storage) => {
+                let Json(settings) = storage.restore(KEY);
+                let mut settings: Settings = settings.unwrap_or_default();
+
+                // Stored themes are checked again in case the validation has changed since
+                settings.custom_themes.retain(|t| t.validate().is_ok());
+                settings.profile = settings.profile.sanitized();
+                settings
+            }
+            None => Settings::new(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut Option<StorageService>) {
+        if let Some(storage) = storage {
+            storage.store(KEY, Json(self));
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,307 @@
+use crate::game::PlayerStatistics;
+use crate::history::GameEvent;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatisticsColumn {
+    Username,
+    CardsPlaced,
+    CardsDrawn,
+    SpecialsPlayed,
+    LongestStreak,
+    TurnsTaken,
+    AverageTurnTime,
+}
+
+impl StatisticsColumn {
+    pub const ALL: [StatisticsColumn; 7] = [
+        StatisticsColumn::Username,
+        StatisticsColumn::CardsPlaced,
+        StatisticsColumn::CardsDrawn,
+        StatisticsColumn::SpecialsPlayed,
+        StatisticsColumn::LongestStreak,
+        StatisticsColumn::TurnsTaken,
+        StatisticsColumn::AverageTurnTime,
+    ];
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            StatisticsColumn::Username => "stats-player",
+            StatisticsColumn::CardsPlaced => "stats-placed",
+            StatisticsColumn::CardsDrawn => "stats-drawn",
+            StatisticsColumn::SpecialsPlayed => "stats-specials",
+            StatisticsColumn::LongestStreak => "stats-streak",
+            StatisticsColumn::TurnsTaken => "stats-turns",
+            StatisticsColumn::AverageTurnTime => "stats-average-turn",
+        }
+    }
+
+    pub fn value(&self, stats: &PlayerStatistics) -> String {
+        match self {
+            StatisticsColumn::Username => stats.username.clone(),
+            StatisticsColumn::CardsPlaced => stats.cards_placed.to_string(),
+            StatisticsColumn::CardsDrawn => stats.cards_drawn.to_string(),
+            StatisticsColumn::SpecialsPlayed => stats.specials_played.to_string(),
+            StatisticsColumn::LongestStreak => stats.longest_streak.to_string(),
+            StatisticsColumn::TurnsTaken => stats.turns_taken.to_string(),
+            StatisticsColumn::AverageTurnTime => format!("{:.1}s", stats.average_turn_time()),
+        }
+    }
+
+    pub fn compare(&self, a: &PlayerStatistics, b: &PlayerStatistics) -> Ordering {
+        match self {
+            StatisticsColumn::Username => a.username.cmp(&b.username),
+            StatisticsColumn::CardsPlaced => a.cards_placed.cmp(&b.cards_placed),
+            StatisticsColumn::CardsDrawn => a.cards_drawn.cmp(&b.cards_drawn),
+            StatisticsColumn::SpecialsPlayed => a.specials_played.cmp(&b.specials_played),
+            StatisticsColumn::LongestStreak => a.longest_streak.cmp(&b.longest_streak),
+            StatisticsColumn::TurnsTaken => a.turns_taken.cmp(&b.turns_taken),
+            StatisticsColumn::AverageTurnTime => a
+                .average_turn_time()
+                .partial_cmp(&b.average_turn_time())
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+pub fn sorted(
+    players: &[PlayerStatistics],
+    column: StatisticsColumn,
+    descending: bool,
+) -> Vec<PlayerStatistics> {
+    let mut players = players.to_vec();
+
+    players.sort_by(|a, b| {
+        let ordering = column.compare(a, b);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    players
+}
+
+pub fn to_csv(players: &[PlayerStatistics]) -> String {
+    let mut csv = String::from(
+        "username,cards_placed,cards_drawn,specials_played,longest_streak,turns_taken,average_turn_time\n",
+    );
+
+    players.iter().for_each(|p| {
+        csv.push_str(&format!(
+            "\"{}\",{},{},{},{},{},{:.3}\n",
+            p.username.replace('"', "\"\""),
+            p.cards_placed,
+            p.cards_drawn,
+            p.specials_played,
+            p.longest_streak,
+            p.turns_taken,
+            p.average_turn_time()
+        ));
+    });
+
+    csv
+}
+
+pub fn to_json(players: &[PlayerStatistics]) -> String {
+    serde_json::to_string_pretty(players).unwrap()
+}
+
+// Collects per-player statistics from the events seen by this client,
+// used when the server doesn't send its own breakdown with the WinUpdate
+pub struct StatisticsTracker {
+    players: Vec<PlayerStatistics>,
+    turn: Option<(Uuid, f64)>, // player, turn start time
+    placed_this_turn: bool,
+    drew_this_turn: bool,
+    streaks: Vec<(Uuid, usize)>,
+}
+
+impl StatisticsTracker {
+    pub fn new() -> StatisticsTracker {
+        StatisticsTracker {
+            players: Vec::new(),
+            turn: None,
+            placed_this_turn: false,
+            drew_this_turn: false,
+            streaks: Vec::new(),
+        }
+    }
+
+    fn player(&mut self, id: Uuid, username: &str) -> &mut PlayerStatistics {
+        let index = match self.players.iter().position(|p| p.id == id) {
+            Some(index) => index,
+            None => {
+                self.players
+                    .push(PlayerStatistics::new(id, username.to_string()));
+                self.players.len() - 1
+            }
+        };
+
+        &mut self.players[index]
+    }
+
+    pub fn record(&mut self, id: Uuid, event: &GameEvent) {
+        let on_turn = self.turn.map(|(turn, _)| turn) == Some(id);
+
+        match event {
+            GameEvent::Placed(username, card) => {
+                let player = self.player(id, username);
+                player.cards_placed += 1;
+                if card.is_special() {
+                    player.specials_played += 1;
+                }
+                self.placed_this_turn |= on_turn;
+            }
+            GameEvent::Drew(username, amount) => {
+                self.player(id, username).cards_drawn += amount;
+                self.drew_this_turn |= on_turn;
+            }
+            GameEvent::Turn(_) | GameEvent::ColorSwitch(..) => {}
+        }
+    }
+
+    pub fn turn(&mut self, id: Uuid, username: &str, now: f64) {
+        self.end_turn(now);
+
+        self.player(id, username);
+        self.turn = Some((id, now));
+    }
+
+    // A streak is a run of consecutive turns where the player placed a card without drawing
+    pub fn end_turn(&mut self, now: f64) {
+        if let Some((id, start)) = self.turn.take() {
+            let streak = match self.streaks.iter().position(|(s, _)| s == &id) {
+                Some(index) => &mut self.streaks[index].1,
+                None => {
+                    self.streaks.push((id, 0));
+                    &mut self.streaks.last_mut().unwrap().1
+                }
+            };
+
+            if self.placed_this_turn && !self.drew_this_turn {
+                *streak += 1;
+            } else {
+                *streak = 0;
+            }
+            let streak = *streak;
+
+            if let Some(player) = self.players.iter_mut().find(|p| p.id == id) {
+                player.turns_taken += 1;
+                player.turn_time += (now - start).max(0.0) as u64;
+                player.longest_streak = player.longest_streak.max(streak);
+            }
+        }
+
+        self.placed_this_turn = false;
+        self.drew_this_turn = false;
+    }
+
+    pub fn finish(&mut self, now: f64) -> Vec<PlayerStatistics> {
+        self.end_turn(now);
+        self.players.clone()
+    }
+}
+
+impl Default for StatisticsTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Card;
+
+    fn stats(username: &str, cards_placed: usize, turn_time: u64) -> PlayerStatistics {
+        let mut stats = PlayerStatistics::new(Uuid::new_v4(), username.to_string());
+        stats.cards_placed = cards_placed;
+        stats.turns_taken = 2;
+        stats.turn_time = turn_time;
+        stats
+    }
+
+    #[test]
+    fn tracks_streaks_and_turn_times() {
+        let (ada, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let placed = |username: &str| {
+            GameEvent::Placed(username.to_string(), Card::new("Block", "Red", Uuid::nil()))
+        };
+        let mut tracker = StatisticsTracker::new();
+
+        tracker.turn(ada, "ada", 0.0);
+        tracker.record(ada, &placed("ada"));
+        tracker.turn(bob, "bob", 1000.0);
+        tracker.record(bob, &GameEvent::Drew("bob".to_string(), 1));
+        tracker.turn(ada, "ada", 3000.0);
+        tracker.record(ada, &placed("ada"));
+        tracker.turn(bob, "bob", 4500.0);
+        tracker.record(bob, &placed("bob"));
+        tracker.turn(ada, "ada", 5000.0);
+        // Drawing ends the streak, but the longest one is kept
+        tracker.record(ada, &GameEvent::Drew("ada".to_string(), 1));
+        tracker.record(ada, &placed("ada"));
+
+        let players = tracker.finish(6000.0);
+        let (ada, bob) = (&players[0], &players[1]);
+        assert_eq!(
+            (ada.cards_placed, ada.specials_played, ada.cards_drawn),
+            (3, 3, 1)
+        );
+        assert_eq!(
+            (ada.turns_taken, ada.turn_time, ada.longest_streak),
+            (3, 3500, 2)
+        );
+        assert_eq!(
+            (bob.turns_taken, bob.turn_time, bob.longest_streak),
+            (2, 2500, 1)
+        );
+        assert_eq!(bob.average_turn_time(), 1.25);
+    }
+
+    #[test]
+    fn sorts_by_column() {
+        let players = vec![
+            stats("bob", 4, 3000),
+            stats("ada", 9, 1000),
+            stats("eve", 1, 8000),
+        ];
+        let names = |column, descending| -> Vec<String> {
+            sorted(&players, column, descending)
+                .into_iter()
+                .map(|p| p.username)
+                .collect()
+        };
+
+        assert_eq!(
+            names(StatisticsColumn::Username, false),
+            vec!["ada", "bob", "eve"]
+        );
+        assert_eq!(
+            names(StatisticsColumn::CardsPlaced, true),
+            vec!["ada", "bob", "eve"]
+        );
+        assert_eq!(
+            names(StatisticsColumn::AverageTurnTime, false),
+            vec!["ada", "bob", "eve"]
+        );
+        assert_eq!(StatisticsColumn::AverageTurnTime.value(&players[2]), "4.0s");
+    }
+
+    #[test]
+    fn exports_csv_and_json() {
+        let players = vec![stats("say \"uno\"", 3, 3000)];
+
+        assert_eq!(
+            to_csv(&players).lines().nth(1),
+            Some("\"say \"\"uno\"\"\",3,0,0,0,2,1.500")
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<PlayerStatistics>>(&to_json(&players)).unwrap(),
+            players
+        );
+    }
+}
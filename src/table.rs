@@ -0,0 +1,64 @@
+use crate::game::Direction;
+use std::f64::consts::PI;
+
+const FAN_STEP: f64 = 4.0; // degrees between neighbouring cards in a fanned hand
+const FAN_MAX: f64 = 40.0; // degrees from the first card to the last
+
+// Position of a seat around the table as percentages of the table's size.
+// Seats go clockwise in play order, starting from the local player at the bottom.
+pub fn seat_position(seat: usize, local: usize, count: usize) -> (f64, f64) {
+    let count = count.max(1);
+    let offset = (seat + count - local % count) % count;
+    let angle = PI / 2.0 + offset as f64 * 2.0 * PI / count as f64;
+
+    (50.0 + 50.0 * angle.cos(), 50.0 + 50.0 * angle.sin())
+}
+
+pub fn direction_class(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Clockwise => "clockwise",
+        Direction::CounterClockwise => "counter-clockwise",
+    }
+}
+
+// Rotation of a card in a fanned hand, the fan is centered and spreads wider with more cards
+pub fn fan_angle(index: usize, count: usize) -> f64 {
+    if count <= 1 {
+        return 0.0;
+    }
+
+    let spread = ((count - 1) as f64 * FAN_STEP).min(FAN_MAX);
+    -spread / 2.0 + spread * index as f64 / (count - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounded((x, y): (f64, f64)) -> (i64, i64) {
+        (x.round() as i64, y.round() as i64)
+    }
+
+    #[test]
+    fn local_player_is_at_the_bottom() {
+        assert_eq!(rounded(seat_position(2, 2, 4)), (50, 100));
+        assert_eq!(rounded(seat_position(0, 0, 1)), (50, 100));
+    }
+
+    #[test]
+    fn seats_go_clockwise() {
+        // bottom, left, top, right
+        assert_eq!(rounded(seat_position(1, 1, 4)), (50, 100));
+        assert_eq!(rounded(seat_position(2, 1, 4)), (0, 50));
+        assert_eq!(rounded(seat_position(3, 1, 4)), (50, 0));
+        assert_eq!(rounded(seat_position(0, 1, 4)), (100, 50));
+    }
+
+    #[test]
+    fn fan_is_centered_and_bounded() {
+        assert_eq!(fan_angle(0, 1), 0.0);
+        assert_eq!(fan_angle(0, 3), -FAN_STEP);
+        assert_eq!(fan_angle(1, 3), 0.0);
+        assert_eq!(fan_angle(29, 30), FAN_MAX / 2.0);
+    }
+}
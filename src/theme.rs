@@ -0,0 +1,172 @@
+use crate::game::Card;
+use crate::i18n::{self, Language};
+use crate::validation::{COLORS, TYPES};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const BUILT_IN: [&str; 3] = [
+    include_str!("../static/themes/default.json"),
+    include_str!("../static/themes/classic.json"),
+    include_str!("../static/themes/felt.json"),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ThemeColors {
+    pub red: String,
+    pub yellow: String,
+    pub green: String,
+    pub blue: String,
+    pub background: String,
+    pub background_darker: String,
+    pub background_lighter: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub colors: ThemeColors,
+    pub card_back: String, // image url
+    pub table: String,     // css background behind the game
+    pub font: String,      // css font-family
+    #[serde(default)]
+    pub card_art: HashMap<String, String>, // "Color.Type" -> image url, empty draws the cards instead
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeError {
+    Malformed(String), // parser error
+    Unnamed,
+    MissingCardArt(String), // "Color.Type"
+}
+
+impl ThemeError {
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            ThemeError::Malformed(e) => {
+                i18n::format(language, "theme-malformed", &[("error", e.as_str().into())])
+            }
+            ThemeError::Unnamed => i18n::tr(language, "theme-unnamed"),
+            ThemeError::MissingCardArt(key) => i18n::format(
+                language,
+                "theme-missing-card-art",
+                &[("card", key.as_str().into())],
+            ),
+        }
+    }
+}
+
+fn art_key(color: &str, r#type: &str) -> String {
+    format!("{}.{}", color, r#type)
+}
+
+impl Theme {
+    // Parses and validates a theme, themes that can't draw every card are rejected
+    pub fn from_json(json: &str) -> Result<Theme, ThemeError> {
+        let theme: Theme =
+            serde_json::from_str(json).map_err(|e| ThemeError::Malformed(e.to_string()))?;
+        theme.validate()?;
+
+        Ok(theme)
+    }
+
+    pub fn validate(&self) -> Result<(), ThemeError> {
+        if self.name.trim().is_empty() {
+            return Err(ThemeError::Unnamed);
+        }
+
+        if self.card_art.is_empty() {
+            return Ok(());
+        }
+
+        COLORS.iter().try_for_each(|color| {
+            TYPES.iter().try_for_each(|r#type| {
+                let key = art_key(color, r#type);
+                match self.card_art.get(&key) {
+                    Some(url) if !url.trim().is_empty() => Ok(()),
+                    _ => Err(ThemeError::MissingCardArt(key)),
+                }
+            })
+        })
+    }
+
+    pub fn built_in() -> Vec<Theme> {
+        BUILT_IN
+            .iter()
+            .filter_map(|json| Theme::from_json(json).ok())
+            .collect()
+    }
+
+    pub fn art(&self, card: &Card) -> Option<&str> {
+        self.card_art
+            .get(&art_key(&card.color, &card.r#type))
+            .map(String::as_str)
+    }
+
+    // red, yellow, green, blue
+    pub fn card_colors(&self) -> [String; 4] {
+        let colors = &self.colors;
+
+        [
+            colors.red.clone(),
+            colors.yellow.clone(),
+            colors.green.clone(),
+            colors.blue.clone(),
+        ]
+    }
+
+    // Overrides the css variables of index.css, the background and the font for everything inside the element
+    pub fn css_variables(&self) -> String {
+        let colors = &self.colors;
+
+        format!(
+            "--red: {}; --yellow: {}; --green: {}; --blue: {}; --disabled: {}; \
+            --background: {}; --background-darker: {}; --background-lighter: {}; \
+            --card-back: url({}); background: {}; font-family: {};",
+            colors.red,
+            colors.yellow,
+            colors.green,
+            colors.blue,
+            colors.red,
+            colors.background,
+            colors.background_darker,
+            colors.background_lighter,
+            self.card_back,
+            self.table,
+            self.font
+        )
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::from_json(BUILT_IN[0]).expect("default theme is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_themes_are_valid() {
+        assert_eq!(Theme::built_in().len(), BUILT_IN.len());
+    }
+
+    #[test]
+    fn rejects_missing_card_art() {
+        let mut theme = Theme::built_in()
+            .into_iter()
+            .find(|t| !t.card_art.is_empty())
+            .unwrap();
+        theme.card_art.remove("Blue.DrawTwo");
+
+        let json = serde_json::to_string(&theme).unwrap();
+        assert_eq!(
+            Theme::from_json(&json),
+            Err(ThemeError::MissingCardArt("Blue.DrawTwo".to_string()))
+        );
+
+        theme.card_art.clear();
+        assert!(Theme::from_json(&serde_json::to_string(&theme).unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,87 @@
+pub const SWIPE_DISTANCE: f64 = 60.0; // px a card has to be pushed up to be played
+pub const HOLD_DURATION: u64 = 450; // ms before a held card is previewed
+const SLOP: f64 = 10.0; // px a finger can drift while still counting as holding still
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap,
+    Hold,
+    SwipeUp,
+    Scroll, // any other movement, left to the browser to scroll the hand
+}
+
+// A finger on one of the cards in the hand, positions are client coordinates in pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardTouch {
+    pub index: usize,
+    pub start: (f64, f64),
+    pub current: (f64, f64),
+    pub started: f64, // ms
+    pub held: bool,
+}
+
+impl CardTouch {
+    pub fn new(index: usize, position: (f64, f64), now: f64) -> CardTouch {
+        CardTouch {
+            index,
+            start: position,
+            current: position,
+            started: now,
+            held: false,
+        }
+    }
+
+    fn delta(&self) -> (f64, f64) {
+        (self.current.0 - self.start.0, self.current.1 - self.start.1)
+    }
+
+    pub fn is_still(&self) -> bool {
+        let (dx, dy) = self.delta();
+        dx.hypot(dy) <= SLOP
+    }
+
+    // How far the card follows the finger upwards while swiping
+    pub fn lift(&self) -> f64 {
+        let (dx, dy) = self.delta();
+        if -dy > dx.abs() {
+            -dy
+        } else {
+            0.0
+        }
+    }
+
+    pub fn gesture(&self, now: f64) -> Gesture {
+        let (dx, dy) = self.delta();
+
+        if -dy >= SWIPE_DISTANCE && -dy > dx.abs() {
+            Gesture::SwipeUp
+        } else if !self.is_still() {
+            Gesture::Scroll
+        } else if self.held || now - self.started >= HOLD_DURATION as f64 {
+            Gesture::Hold
+        } else {
+            Gesture::Tap
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_gestures() {
+        let mut touch = CardTouch::new(0, (100.0, 500.0), 0.0);
+        assert_eq!(touch.gesture(100.0), Gesture::Tap);
+        assert_eq!(touch.gesture(HOLD_DURATION as f64), Gesture::Hold);
+
+        touch.current = (105.0, 420.0);
+        assert_eq!(touch.gesture(100.0), Gesture::SwipeUp);
+        assert_eq!(touch.lift(), 80.0);
+
+        // Sideways movement scrolls the hand
+        touch.current = (200.0, 440.0);
+        assert_eq!(touch.gesture(100.0), Gesture::Scroll);
+        assert_eq!(touch.lift(), 0.0);
+    }
+}
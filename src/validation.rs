@@ -0,0 +1,343 @@
+use crate::chat::Channel;
+use crate::game::{Card, Player};
+use crate::i18n::{self, Language};
+use crate::matchmaking;
+use crate::packets::PacketType;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const COLORS: [&str; 4] = ["Red", "Yellow", "Green", "Blue"];
+pub const TYPES: [&str; 15] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Block",
+    "Reverse", "DrawTwo", "DrawFour", "Switch",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PacketError {
+    Malformed(String),   // parser error
+    UnknownPlayer(Uuid), // id
+    InvalidCard(Card),   // card
+    NotInHand(Card),     // card
+    NotSeated(Uuid),     // id
+}
+
+impl PacketError {
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            PacketError::Malformed(e) => i18n::format(
+                language,
+                "packet-malformed",
+                &[("error", e.as_str().into())],
+            ),
+            PacketError::UnknownPlayer(id) => i18n::format(
+                language,
+                "packet-unknown-player",
+                &[("id", id.to_string().into())],
+            ),
+            // Invalid cards have no translated name, they are shown as received
+            PacketError::InvalidCard(card) => i18n::format(
+                language,
+                "packet-invalid-card",
+                &[("card", format!("{} {}", card.color, card.r#type).into())],
+            ),
+            PacketError::NotInHand(card) => i18n::format(
+                language,
+                "packet-not-in-hand",
+                &[("card", i18n::card_name(language, card).into())],
+            ),
+            PacketError::NotSeated(id) => i18n::format(
+                language,
+                "packet-not-seated",
+                &[("id", id.to_string().into())],
+            ),
+        }
+    }
+}
+
+// What the client currently knows, packets contradicting it are rejected
+pub struct PacketContext<'a> {
+    pub id: Option<Uuid>,
+    pub connections: &'a HashMap<Uuid, Player>,
+    pub cards: &'a [Card],
+}
+
+impl PacketContext<'_> {
+    fn player(&self, id: &Uuid) -> Result<(), PacketError> {
+        if self.id.as_ref() == Some(id) || self.connections.contains_key(id) {
+            Ok(())
+        } else {
+            Err(PacketError::UnknownPlayer(*id))
+        }
+    }
+
+    // Players other than this client
+    fn opponent(&self, id: &Uuid) -> Result<(), PacketError> {
+        if self.connections.contains_key(id) {
+            Ok(())
+        } else {
+            Err(PacketError::UnknownPlayer(*id))
+        }
+    }
+}
+
+fn card(card: &Card) -> Result<(), PacketError> {
+    if COLORS.contains(&card.color.as_str()) && TYPES.contains(&card.r#type.as_str()) {
+        Ok(())
+    } else {
+        Err(PacketError::InvalidCard(card.clone()))
+    }
+}
+
+pub fn validate(packet: &PacketType, context: &PacketContext) -> Result<(), PacketError> {
+    match packet {
+        PacketType::Disconnect(id, _) => context.player(id),
+        // Our own status comes with StatusUpdatePrivate
+        PacketType::StatusUpdatePublic(id, _, _, current) => {
+            context.opponent(id)?;
+            card(current)
+        }
+        PacketType::StatusUpdatePrivate(cards, current) => {
+            cards.iter().try_for_each(card)?;
+            card(current)
+        }
+        PacketType::AllowedCardsUpdate(cards) => cards.iter().try_for_each(|c| {
+            if context.cards.contains(c) {
+                Ok(())
+            } else {
+                Err(PacketError::NotInHand(c.clone()))
+            }
+        }),
+        PacketType::TurnUpdate(current, next) => {
+            context.player(current)?;
+            context.player(next)
+        }
+        PacketType::RematchVoteUpdate(id, _) => context.player(id),
+        PacketType::ReactionUpdate(id, ..) => context.player(id),
+        PacketType::EmoteUpdate(id, _) => context.player(id),
+        PacketType::IdentityUpdate(id, ..) => context.player(id),
+        PacketType::LeaderboardUpdate(entries) => {
+            match entries.iter().find(|e| !e.rating.is_finite()) {
+                Some(entry) => Err(PacketError::Malformed(format!(
+                    "rating of {} is not a number",
+                    entry.username
+                ))),
+                None => Ok(()),
+            }
+        }
+        PacketType::MatchFound(room) | PacketType::InviteReceived(_, _, room) => {
+            if matchmaking::valid_room(room) {
+                Ok(())
+            } else {
+                Err(PacketError::Malformed(format!("invalid room {}", room)))
+            }
+        }
+        PacketType::ChannelMessageUpdate(_, sender, channel, _) => {
+            context.player(sender)?;
+            match channel {
+                Channel::Whisper(recipient) => context.player(recipient),
+                _ => Ok(()),
+            }
+        }
+        PacketType::SeatOrder(seats) => seats.iter().try_for_each(|id| context.player(id)),
+        PacketType::StateSnapshot(snapshot) => {
+            let seated = |id: &Uuid| {
+                if snapshot.seats.iter().any(|s| &s.id == id) {
+                    Ok(())
+                } else {
+                    Err(PacketError::NotSeated(*id))
+                }
+            };
+
+            // Players joining a running game watch it until the next round
+            if let Some(id) = context.id.as_ref().filter(|_| !snapshot.active) {
+                seated(id)?;
+            }
+            snapshot.turn.iter().try_for_each(seated)?;
+            snapshot.next.iter().try_for_each(seated)?;
+
+            snapshot.current.iter().try_for_each(card)?;
+            snapshot.cards.iter().try_for_each(card)?;
+            snapshot.allowed_cards.iter().try_for_each(|c| {
+                if snapshot.cards.contains(c) {
+                    Ok(())
+                } else {
+                    Err(PacketError::NotInHand(c.clone()))
+                }
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Direction, GameOptions, Seat, StateSnapshot};
+
+    #[test]
+    fn rejects_unknown_player() {
+        let (id, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let connections = HashMap::new();
+        let context = PacketContext {
+            id: Some(id),
+            connections: &connections,
+            cards: &[],
+        };
+
+        let current = Card::new("Seven", "Red", other);
+
+        assert_eq!(
+            validate(
+                &PacketType::StatusUpdatePublic(other, "bob".to_string(), 3, current),
+                &context
+            ),
+            Err(PacketError::UnknownPlayer(other))
+        );
+        assert_eq!(
+            validate(&PacketType::TurnUpdate(id, other), &context),
+            Err(PacketError::UnknownPlayer(other))
+        );
+    }
+
+    #[test]
+    fn rejects_public_status_of_self() {
+        let id = Uuid::new_v4();
+        let connections = HashMap::new();
+        let context = PacketContext {
+            id: Some(id),
+            connections: &connections,
+            cards: &[],
+        };
+
+        let current = Card::new("Seven", "Red", id);
+
+        assert_eq!(
+            validate(
+                &PacketType::StatusUpdatePublic(id, "alice".to_string(), 3, current),
+                &context
+            ),
+            Err(PacketError::UnknownPlayer(id))
+        );
+    }
+
+    #[test]
+    fn rejects_inconsistent_cards() {
+        let id = Uuid::new_v4();
+        let mut connections = HashMap::new();
+        connections.insert(id, Player::new("bob".to_string(), 7, 2));
+
+        let hand = vec![Card::new("Seven", "Red", id)];
+        let context = PacketContext {
+            id: None,
+            connections: &connections,
+            cards: &hand,
+        };
+
+        let invalid = Card::new("Eleven", "Purple", id);
+        let missing = Card::new("Two", "Blue", id);
+
+        assert_eq!(
+            validate(
+                &PacketType::StatusUpdatePublic(id, "bob".to_string(), 6, invalid.clone()),
+                &context
+            ),
+            Err(PacketError::InvalidCard(invalid))
+        );
+        assert_eq!(
+            validate(
+                &PacketType::AllowedCardsUpdate(vec![missing.clone()]),
+                &context
+            ),
+            Err(PacketError::NotInHand(missing))
+        );
+        assert_eq!(
+            validate(&PacketType::AllowedCardsUpdate(hand.clone()), &context),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_snapshot_without_self() {
+        let (id, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let connections = HashMap::new();
+        let context = PacketContext {
+            id: Some(id),
+            connections: &connections,
+            cards: &[],
+        };
+
+        let mut snapshot = StateSnapshot {
+            seats: vec![Seat {
+                id: other,
+                username: "bob".to_string(),
+                card_count: 7,
+            }],
+            active: true,
+            current: Some(Card::new("Two", "Green", other)),
+            color: None,
+            direction: Direction::Clockwise,
+            turn: Some(other),
+            next: Some(id),
+            cards: Vec::new(),
+            allowed_cards: Vec::new(),
+            options: GameOptions::new(),
+            spectators: Vec::new(),
+        };
+
+        assert_eq!(
+            validate(&PacketType::StateSnapshot(snapshot.clone()), &context),
+            Err(PacketError::NotSeated(id))
+        );
+
+        // Watching a running game
+        snapshot.next = Some(other);
+        snapshot.spectators.push(Seat {
+            id,
+            username: "alice".to_string(),
+            card_count: 0,
+        });
+        assert_eq!(
+            validate(&PacketType::StateSnapshot(snapshot.clone()), &context),
+            Ok(())
+        );
+
+        snapshot.active = false;
+        assert_eq!(
+            validate(&PacketType::StateSnapshot(snapshot.clone()), &context),
+            Err(PacketError::NotSeated(id))
+        );
+
+        snapshot.spectators.clear();
+
+        snapshot.seats.push(Seat {
+            id,
+            username: "alice".to_string(),
+            card_count: 0,
+        });
+
+        assert_eq!(
+            validate(&PacketType::StateSnapshot(snapshot), &context),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn describes_errors_in_the_language() {
+        let id = Uuid::new_v4();
+        let invalid = PacketError::InvalidCard(Card::new("Eleven", "Purple", id));
+        let missing = PacketError::NotInHand(Card::new("Two", "Blue", id));
+
+        assert_eq!(
+            invalid.describe(Language::English),
+            "invalid card Purple Eleven"
+        );
+        assert_eq!(
+            missing.describe(Language::English),
+            "allowed card Blue Two is not in hand"
+        );
+        assert_eq!(
+            missing.describe(Language::Finnish),
+            "sallittu kortti Sininen kaksi ei ole kädessä"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+# English, also the fallback for messages missing from other languages
+
+language = Language
+
+## Connecting
+
+connect-title = Enter Room ID
+room-id-placeholder = Please enter a valid room-id
+connect = Connect
+connect-failed = Failed to connect to servers
+username-title = Enter your username
+username-placeholder = Please enter a valid username
+register = Register
+connected = Connected:
+online = Online
+offline = Offline
+
+## Waiting room
+
+waiting-for-game = Waiting for game to start
+waiting-for-round = Waiting for round { $round } to start
+room-id = This room's ID
+invalid-room-id = Invalid ID, please refresh your page.
+rematch-votes = { $votes }/{ $players } players want a rematch
+host = You are the host
+score-target-placeholder = Points to win, e.g. { $target } (empty for a single round)
+start-game = Start game
+start-round = Start next round
+scoreboard-title = Scoreboard - first to { $target } points
+rounds-played = { $n ->
+    [one] One round played
+   *[other] { $n } rounds played
+}
+
+## Playing
+
+your-hand = Your hand
+your-turn = Your turn.
+waiting-for-opponent = Waiting for the opponent
+place-card = Place a card.
+place-card-touch = Tap or swipe up to play, hold to see a card
+end-turn = End your turn
+end-turn-label = End your turn (E)
+draw-card = Draw a card.
+draw-card-label = Draw a card (D)
+select-color = Select the color you want to switch to
+color-label = { $color } ({ $key })
+resync = Resync
+wins = { $wins }W
+you = You
+default-username = player
+unknown-player = Unknown
+self-username = { $username } [You]
+unset = unset
+next = [Next]
+turn = [Turn]
+announce-turn = It's { $username }'s turn
+announce-own-turn = It's your turn
+invalid-update = Received an invalid update from the server: { $error }
+packet-malformed = malformed packet ({ $error })
+packet-unknown-player = unknown player { $id }
+packet-invalid-card = invalid card { $card }
+packet-not-in-hand = allowed card { $card } is not in hand
+packet-not-seated = player { $id } is not seated
+
+## Cards
+
+color-red = Red
+color-yellow = Yellow
+color-green = Green
+color-blue = Blue
+type-zero = Zero
+type-one = One
+type-two = Two
+type-three = Three
+type-four = Four
+type-five = Five
+type-six = Six
+type-seven = Seven
+type-eight = Eight
+type-nine = Nine
+type-block = Block
+type-reverse = Reverse
+type-draw-two = Draw Two
+type-draw-four = Draw Four
+type-switch = Switch
+card-name = { $color } { $type }
+card-playable = { $card }, playable
+card-not-playable = { $card }, not playable
+
+## Game log
+
+log-all = All
+log-plays = Plays
+log-draws = Draws
+log-turns = Turns
+log-colors = Colors
+log-export = Export
+event-placed = { $username } played { $card }
+event-drew = { $n ->
+    [one] { $username } drew a card
+   *[other] { $username } drew { $n } cards
+}
+event-turn = { $username }'s turn
+event-color = { $username } switched the color to { $color }
+
+## End of the game
+
+game-ended = Game Ended
+game-lasted = Game lasted
+duration = { $minutes }min { $seconds }s
+unknown-duration = an unknown time
+players-took-part = { $n ->
+    [one] player took part
+   *[other] players took part
+}
+spectators-took-part = { $n ->
+    [one] spectator took part
+   *[other] spectators took part
+}
+cards-were-drawn = { $n ->
+    [one] card was drawn
+   *[other] cards were drawn
+}
+cards-were-placed = { $n ->
+    [one] card was placed
+   *[other] cards were placed
+}
+winner = { $username } won
+round-points = +{ $points } points
+round-points-unknown = Points for this round are unknown
+match-winner = { $username } wins the match
+stats-player = Player
+stats-placed = Placed
+stats-drawn = Drawn
+stats-specials = Specials
+stats-streak = Streak
+stats-turns = Turns
+stats-average-turn = Avg turn
+export-json = Export JSON
+export-csv = Export CSV
+rematch = Rematch
+leave = Leave
+
+## Settings
+
+mute = Mute
+unmute = Unmute
+settings = Settings
+volume = Volume
+muted = Muted
+palette = Palette
+palette-default = Default
+palette-deuteranopia = Deuteranopia
+palette-protanopia = Protanopia
+palette-tritanopia = Tritanopia
+theme = Theme
+load-theme = Load theme
+theme-rejected = Theme rejected, { $error }
+theme-malformed = malformed theme ({ $error })
+theme-unnamed = theme has no name
+theme-missing-card-art = theme has no card art for { $card }
+color-glyphs = Color symbols on cards
+keys-help = Keys: ← → pick a card, Enter play, D draw, E end turn, 1-4 pick a color
+close = Close
+
+## Chat
+
+chat-connected = [Connected]
+chat-disconnected = [Disconnected]
+chat-message = [Message]
+chat-warning = [Warning]
+chat-error = [Error]
+chat-client = Client
+chat-server = Server
+chat-placeholder = Send a message to chat
+chat-show = Chat
+chat-hide = Hide chat
+chat-older = Show older messages
+unread-messages = { $n ->
+    [one] One new message
+   *[other] { $n } new messages
+}
+channel-room = Room
+channel-spectators = Spectators
+channel-whisper-left = Left the room
+whisper-to = Whisper to { $username }
+chat-read-only = You can't write here right now
+mute-player = Mute { $username }
+block-player = Block { $username }
+unblock-player = Unblock { $username }
+word-filter = Filtered words
+word-filter-hint = Separated by commas
+blocked-players = Blocked players
+no-blocked-players = Nobody is blocked
+chat-too-fast = { $seconds ->
+    [one] You're sending messages too fast, wait a second
+   *[other] You're sending messages too fast, wait { $seconds } seconds
+}
+emote-nice = Nice!
+emote-oops = Oops
+emote-good-game = GG
+show-emotes = Show emotes
+avatar = Avatar
+avatar-color = Avatar color
+bio-placeholder = A few words about yourself
+identity = Identity
+account = Account
+account-placeholder = Account name
+claim-account = Create account
+account-of = Account { $account }
+account-name-invalid = Account names are 3 to { $max } letters, numbers, - or _
+account-offline = Connect to a server to create an account
+history = History
+history-empty = No finished games yet
+history-date = Date
+history-place = Place
+history-opponents = Opponents
+history-duration = Duration
+history-cards = Placed / drawn
+history-placement = { $place } / { $players }
+history-watched = Watched
+history-clear = Clear history
+lifetime-games = Games played
+lifetime-wins = Wins
+lifetime-win-rate = Win rate
+lifetime-average-place = Average place
+lifetime-cards-placed = Cards placed per game
+lifetime-cards-drawn = Cards drawn per game
+leaderboard = Leaderboard
+leaderboard-server = Server
+leaderboard-local = This device
+leaderboard-offline = Not connected, showing the ratings from this device
+leaderboard-loading = Loading…
+rating = Rating
+rating-change = Your rating { $rating } ({ $change })
+quick-play = Quick Play
+queue-searching = Looking for a game…
+queue-position = Position in queue: { $position }
+queue-estimate = Estimated wait { $time }
+queue-estimating = Estimating the wait…
+queue-waited = Waited { $time }
+queue-failed = Couldn't reach matchmaking
+queue-closed = Matchmaking closed the connection, try again
+cancel = Cancel
+friends = Friends
+no-friends = Add friends from the player list in a room
+presence-offline = Not connected to the presence server, friends show as offline
+presence-away = Offline
+presence-online = Online
+presence-in-room = In a room
+presence-here = In this room
+invite = Invite
+remove-friend = Remove
+add-friend = Add { $username } as a friend
+invite-received = { $username } invited you to their room
+join = Join
+dismiss = Dismiss
+send = Send
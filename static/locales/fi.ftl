@@ -0,0 +1,259 @@
+# Finnish
+
+language = Kieli
+
+## Connecting
+
+connect-title = Syötä huoneen tunnus
+room-id-placeholder = Syötä kelvollinen huoneen tunnus
+connect = Yhdistä
+connect-failed = Palvelimiin ei saatu yhteyttä
+username-title = Syötä käyttäjänimesi
+username-placeholder = Syötä kelvollinen käyttäjänimi
+register = Rekisteröidy
+connected = Yhdistetty:
+online = Yhteydessä
+offline = Ei yhteyttä
+
+## Waiting room
+
+waiting-for-game = Odotetaan pelin alkua
+waiting-for-round = Odotetaan kierroksen { $round } alkua
+room-id = Huoneen tunnus
+invalid-room-id = Virheellinen tunnus, päivitä sivu.
+rematch-votes = { $votes }/{ $players } pelaajaa haluaa uusintaottelun
+host = Olet isäntä
+score-target-placeholder = Voittoon vaadittavat pisteet, esim. { $target } (tyhjä yhdelle kierrokselle)
+start-game = Aloita peli
+start-round = Aloita seuraava kierros
+scoreboard-title = Pistetaulukko - voittoon { $target } pistettä
+rounds-played = { $n ->
+    [one] Yksi kierros pelattu
+   *[other] { $n } kierrosta pelattu
+}
+
+## Playing
+
+your-hand = Kätesi
+your-turn = Sinun vuorosi.
+waiting-for-opponent = Odotetaan vastustajaa
+place-card = Pelaa kortti.
+place-card-touch = Pelaa napauttamalla tai pyyhkäisemällä ylös, näe kortti painamalla pitkään
+end-turn = Lopeta vuorosi
+end-turn-label = Lopeta vuorosi (E)
+draw-card = Nosta kortti.
+draw-card-label = Nosta kortti (D)
+select-color = Valitse väri, johon haluat vaihtaa
+color-label = { $color } ({ $key })
+resync = Synkronoi
+wins = { $wins }V
+you = Sinä
+default-username = pelaaja
+unknown-player = Tuntematon
+self-username = { $username } [Sinä]
+unset = ei asetettu
+next = [Seuraava]
+turn = [Vuoro]
+announce-turn = Pelaajan { $username } vuoro
+announce-own-turn = Sinun vuorosi
+invalid-update = Palvelimelta saatiin virheellinen päivitys: { $error }
+packet-malformed = viallinen paketti ({ $error })
+packet-unknown-player = tuntematon pelaaja { $id }
+packet-invalid-card = virheellinen kortti { $card }
+packet-not-in-hand = sallittu kortti { $card } ei ole kädessä
+packet-not-seated = pelaaja { $id } ei istu pöydässä
+
+## Cards
+
+color-red = Punainen
+color-yellow = Keltainen
+color-green = Vihreä
+color-blue = Sininen
+type-zero = nolla
+type-one = yksi
+type-two = kaksi
+type-three = kolme
+type-four = neljä
+type-five = viisi
+type-six = kuusi
+type-seven = seitsemän
+type-eight = kahdeksan
+type-nine = yhdeksän
+type-block = ohitus
+type-reverse = suunnanvaihto
+type-draw-two = nosta kaksi
+type-draw-four = nosta neljä
+type-switch = värinvaihto
+card-name = { $color } { $type }
+card-playable = { $card }, pelattavissa
+card-not-playable = { $card }, ei pelattavissa
+
+## Game log
+
+log-all = Kaikki
+log-plays = Pelatut
+log-draws = Nostot
+log-turns = Vuorot
+log-colors = Värit
+log-export = Vie
+event-placed = { $username } pelasi kortin { $card }
+event-drew = { $n ->
+    [one] { $username } nosti kortin
+   *[other] { $username } nosti { $n } korttia
+}
+event-turn = Pelaajan { $username } vuoro
+event-color = { $username } vaihtoi värin: { $color }
+
+## End of the game
+
+game-ended = Peli päättyi
+game-lasted = Peli kesti
+duration = { $minutes } min { $seconds } s
+unknown-duration = tuntemattoman ajan
+players-took-part = { $n ->
+    [one] pelaaja osallistui
+   *[other] pelaajaa osallistui
+}
+spectators-took-part = { $n ->
+    [one] katsoja osallistui
+   *[other] katsojaa osallistui
+}
+cards-were-drawn = { $n ->
+    [one] kortti nostettiin
+   *[other] korttia nostettiin
+}
+cards-were-placed = { $n ->
+    [one] kortti pelattiin
+   *[other] korttia pelattiin
+}
+winner = { $username } voitti
+round-points = +{ $points } pistettä
+round-points-unknown = Kierroksen pisteet eivät ole tiedossa
+match-winner = { $username } voittaa ottelun
+stats-player = Pelaaja
+stats-placed = Pelatut
+stats-drawn = Nostetut
+stats-specials = Erikoiskortit
+stats-streak = Putki
+stats-turns = Vuorot
+stats-average-turn = Vuoron ka.
+export-json = Vie JSON
+export-csv = Vie CSV
+rematch = Uusintaottelu
+leave = Poistu
+
+## Settings
+
+mute = Mykistä
+unmute = Poista mykistys
+settings = Asetukset
+volume = Äänenvoimakkuus
+muted = Mykistetty
+palette = Väripaletti
+palette-default = Oletus
+palette-deuteranopia = Deuteranopia
+palette-protanopia = Protanopia
+palette-tritanopia = Tritanopia
+theme = Teema
+load-theme = Lataa teema
+theme-rejected = Teema hylättiin, { $error }
+theme-malformed = viallinen teema ({ $error })
+theme-unnamed = teemalla ei ole nimeä
+theme-missing-card-art = teemasta puuttuu kortin { $card } kuva
+color-glyphs = Värisymbolit korteissa
+keys-help = Näppäimet: ← → valitse kortti, Enter pelaa, D nosta, E lopeta vuoro, 1-4 valitse väri
+close = Sulje
+
+## Chat
+
+chat-connected = [Yhdistyi]
+chat-disconnected = [Poistui]
+chat-message = [Viesti]
+chat-warning = [Varoitus]
+chat-error = [Virhe]
+chat-client = Asiakas
+chat-server = Palvelin
+chat-placeholder = Lähetä viesti chattiin
+chat-show = Näytä chat
+chat-hide = Piilota chat
+chat-older = Näytä vanhemmat viestit
+unread-messages = { $n ->
+    [one] Yksi uusi viesti
+   *[other] { $n } uutta viestiä
+}
+channel-room = Huone
+channel-spectators = Katsojat
+channel-whisper-left = Poistui huoneesta
+whisper-to = Kuiskaa pelaajalle { $username }
+chat-read-only = Et voi kirjoittaa tänne juuri nyt
+mute-player = Mykistä { $username }
+block-player = Estä { $username }
+unblock-player = Poista esto: { $username }
+word-filter = Suodatetut sanat
+word-filter-hint = Pilkuilla erotettuina
+blocked-players = Estetyt pelaajat
+no-blocked-players = Ketään ei ole estetty
+chat-too-fast = { $seconds ->
+    [one] Lähetät viestejä liian nopeasti, odota sekunti
+   *[other] Lähetät viestejä liian nopeasti, odota { $seconds } sekuntia
+}
+emote-nice = Hienoa!
+emote-oops = Hups
+emote-good-game = GG
+show-emotes = Näytä emojit
+avatar = Profiilikuva
+avatar-color = Profiilikuvan väri
+bio-placeholder = Muutama sana itsestäsi
+identity = Tunniste
+account = Tili
+account-placeholder = Tilin nimi
+claim-account = Luo tili
+account-of = Tili { $account }
+account-name-invalid = Tilin nimessä on 3–{ $max } kirjainta, numeroa, - tai _
+account-offline = Yhdistä palvelimeen luodaksesi tilin
+history = Historia
+history-empty = Ei vielä päättyneitä pelejä
+history-date = Päivä
+history-place = Sija
+history-opponents = Vastustajat
+history-duration = Kesto
+history-cards = Pelattu / nostettu
+history-placement = { $place } / { $players }
+history-watched = Katsottu
+history-clear = Tyhjennä historia
+lifetime-games = Pelattuja pelejä
+lifetime-wins = Voittoja
+lifetime-win-rate = Voittoprosentti
+lifetime-average-place = Keskimääräinen sija
+lifetime-cards-placed = Pelattuja kortteja per peli
+lifetime-cards-drawn = Nostettuja kortteja per peli
+leaderboard = Tulostaulu
+leaderboard-server = Palvelin
+leaderboard-local = Tämä laite
+leaderboard-offline = Ei yhteyttä, näytetään tämän laitteen luokitukset
+leaderboard-loading = Ladataan…
+rating = Luokitus
+rating-change = Luokituksesi { $rating } ({ $change })
+quick-play = Pikapeli
+queue-searching = Etsitään peliä…
+queue-position = Sija jonossa: { $position }
+queue-estimate = Arvioitu odotus { $time }
+queue-estimating = Arvioidaan odotusta…
+queue-waited = Odotettu { $time }
+queue-failed = Pelinhakuun ei saatu yhteyttä
+queue-closed = Pelinhaku sulki yhteyden, yritä uudelleen
+cancel = Peruuta
+friends = Kaverit
+no-friends = Lisää kavereita huoneen pelaajalistasta
+presence-offline = Ei yhteyttä tilapalvelimeen, kaverit näkyvät poissa olevina
+presence-away = Poissa
+presence-online = Paikalla
+presence-in-room = Huoneessa
+presence-here = Tässä huoneessa
+invite = Kutsu
+remove-friend = Poista
+add-friend = Lisää { $username } kaveriksi
+invite-received = { $username } kutsui sinut huoneeseensa
+join = Liity
+dismiss = Ohita
+send = Lähetä